@@ -0,0 +1,146 @@
+use crate::Gramatica;
+
+// símbolo usado para representar o fim da entrada nos conjuntos FOLLOW
+pub const FIM_DA_ENTRADA: &str = "$";
+
+// conjuntos NULLABLE, FIRST e FOLLOW de cada não terminal, indexados na mesma
+// ordem de gramatica.nao_terminais
+#[derive(Debug, Clone)]
+pub struct Conjuntos {
+    pub anulaveis: Vec<bool>,
+    pub primeiros: Vec<Vec<String>>,
+    pub seguintes: Vec<Vec<String>>,
+}
+
+impl Gramatica {
+    pub fn indice_nao_terminal(&self, simbolo: &str) -> Option<usize> {
+        self.nao_terminais.iter().position(|s| s == simbolo)
+    }
+
+    pub fn calcula_conjuntos(&self) -> Conjuntos {
+        let mut conjuntos = Conjuntos {
+            anulaveis: vec![false; self.nao_terminais.len()],
+            primeiros: vec![Vec::new(); self.nao_terminais.len()],
+            seguintes: vec![Vec::new(); self.nao_terminais.len()],
+        };
+
+        // NULLABLE e FIRST: repete até que nenhum conjunto mude
+        let mut mudou = true;
+        while mudou {
+            mudou = false;
+            for regra in &self.regras {
+                let a = self.indice_nao_terminal(&regra.nao_terminal).unwrap();
+                let (primeiros, anulavel) = conjuntos.primeiros_da_sequencia(self, &regra.producao);
+                for terminal in primeiros {
+                    mudou |= adiciona(&mut conjuntos.primeiros[a], terminal);
+                }
+                if anulavel && !conjuntos.anulaveis[a] {
+                    conjuntos.anulaveis[a] = true;
+                    mudou = true;
+                }
+            }
+        }
+
+        // FOLLOW: o fim da entrada segue o símbolo inicial
+        if !self.nao_terminais.is_empty() {
+            conjuntos.seguintes[0].push(FIM_DA_ENTRADA.to_string());
+        }
+        mudou = true;
+        while mudou {
+            mudou = false;
+            for regra in &self.regras {
+                let a = self.indice_nao_terminal(&regra.nao_terminal).unwrap();
+                for (i, simbolo) in regra.producao.iter().enumerate() {
+                    if let Some(b) = self.indice_nao_terminal(simbolo) {
+                        let (primeiros, anulavel) = conjuntos.primeiros_da_sequencia(self, &regra.producao[i + 1..]);
+                        for terminal in primeiros {
+                            mudou |= adiciona(&mut conjuntos.seguintes[b], terminal);
+                        }
+                        if anulavel {
+                            for terminal in conjuntos.seguintes[a].clone() {
+                                mudou |= adiciona(&mut conjuntos.seguintes[b], terminal);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        conjuntos
+    }
+}
+
+impl Conjuntos {
+    // FIRST de uma sequência de símbolos e se a sequência inteira é anulável
+    pub fn primeiros_da_sequencia(&self, gramatica: &Gramatica, simbolos: &[String]) -> (Vec<String>, bool) {
+        let mut primeiros: Vec<String> = Vec::new();
+        for simbolo in simbolos {
+            match gramatica.indice_nao_terminal(simbolo) {
+                Some(b) => {
+                    for terminal in &self.primeiros[b] {
+                        adiciona(&mut primeiros, terminal.to_string());
+                    }
+                    if !self.anulaveis[b] {
+                        return (primeiros, false);
+                    }
+                }
+                None => {
+                    adiciona(&mut primeiros, simbolo.to_string());
+                    return (primeiros, false);
+                }
+            }
+        }
+        (primeiros, true)
+    }
+}
+
+impl Conjuntos {
+    // seção do relatório com NULLABLE, FIRST e FOLLOW, em texto e em Markdown
+    pub fn formata(&self, gramatica: &Gramatica) -> String {
+        format!(
+            "\n---------------------------------\nConjuntos:\n{}\n{}\n",
+            self.formata_texto(gramatica),
+            self.formata_md(gramatica),
+        )
+    }
+
+    // um não terminal por linha, sem o da regra aumentada:
+    //   T: anulável: não, FIRST = {Id, AbreP}, FOLLOW = {$, Mult, FechaP}
+    pub fn formata_texto(&self, gramatica: &Gramatica) -> String {
+        let mut string = String::new();
+        for (a, nao_terminal) in gramatica.nao_terminais.iter().enumerate().skip(1) {
+            string += format!(
+                "  {}: anulável: {}, FIRST = {{{}}}, FOLLOW = {{{}}}\n",
+                nao_terminal,
+                if self.anulaveis[a] { "sim" } else { "não" },
+                self.primeiros[a].join(", "),
+                self.seguintes[a].join(", "),
+            ).as_ref();
+        }
+        string
+    }
+
+    pub fn formata_md(&self, gramatica: &Gramatica) -> String {
+        let mut string = "| Não terminal | Anulável | FIRST | FOLLOW |\n|---|---|---|---|\n".to_string();
+        for (a, nao_terminal) in gramatica.nao_terminais.iter().enumerate().skip(1) {
+            string += format!(
+                "| {} | {} | {} | {} |\n",
+                nao_terminal,
+                if self.anulaveis[a] { "sim" } else { "não" },
+                self.primeiros[a].join(", "),
+                self.seguintes[a].join(", "),
+            ).as_ref();
+        }
+        string
+    }
+}
+
+// adiciona o símbolo ao conjunto caso ainda não esteja presente; devolve se
+// o conjunto mudou
+pub(crate) fn adiciona(conjunto: &mut Vec<String>, simbolo: String) -> bool {
+    if conjunto.contains(&simbolo) {
+        return false;
+    }
+    conjunto.push(simbolo);
+    true
+}
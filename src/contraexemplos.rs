@@ -0,0 +1,406 @@
+use std::cmp::Reverse;
+use std::collections::{HashSet, VecDeque};
+
+use crate::conjuntos::FIM_DA_ENTRADA;
+use crate::{Acao, Automato, Conflito, Gramatica};
+
+// limites das buscas, para que gramáticas grandes ou cíclicas não travem o relatório
+const LIMITE_DE_CONFIGURACOES: usize = 200_000;
+const LIMITE_DA_PILHA: usize = 64;
+
+// entrada de exemplo e a posição, antes do símbolo de entrada do conflito,
+// em que o conflito ocorre
+type Exemplo = (Vec<String>, usize);
+
+// ações seguidas por uma derivação, cada uma com a coluna do símbolo de entrada
+type Historico = Vec<(Acao, usize)>;
+
+// exemplos de entrada para um conflito
+#[derive(Debug, Clone)]
+pub struct Contraexemplo {
+    // símbolos do menor caminho de I0 até o estado do conflito
+    pub caminho: Vec<String>,
+    // para cada ação do conflito, uma entrada completa que a usa, com a
+    // posição do conflito na entrada; None se nenhuma foi encontrada
+    pub exemplos: Vec<(Acao, Option<Exemplo>)>,
+    // uma das entradas com duas derivações diferentes, quando a gramática é
+    // ambígua: a entrada e as duas árvores
+    pub derivacoes: Option<(Vec<String>, String, String)>,
+}
+
+// analisador LR não determinístico: em cada célula segue todas as ações
+// candidatas, não só a mantida na tabela
+struct Simulador<'a> {
+    automato: &'a Automato,
+    // terminais seguidos do $, na ordem das colunas da tabela
+    colunas: Vec<String>,
+    acoes: Vec<Vec<Vec<Acao>>>,
+    desvios: Vec<Vec<(String, usize)>>,
+    // menor sentença, em colunas, de cada não terminal, na ordem de
+    // gramatica.nao_terminais; None se ele não deriva nenhuma
+    menores_sentencas: Vec<Option<Vec<usize>>>,
+}
+
+impl Automato {
+    pub fn contraexemplo(&self, conflito: &Conflito) -> Contraexemplo {
+        Simulador::novo(self).contraexemplo(conflito)
+    }
+
+    // contraexemplos de todos os conflitos não resolvidos, na ordem de
+    // self.conflitos, com as tabelas do simulador montadas uma vez só
+    pub fn contraexemplos(&self) -> Vec<Contraexemplo> {
+        let simulador = Simulador::novo(self);
+        self.conflitos.iter().map(|conflito| simulador.contraexemplo(conflito)).collect()
+    }
+}
+
+impl Contraexemplo {
+    pub fn formata(&self) -> String {
+        let mut string = format!("  estado alcançado por: {}\n", self.caminho.join(" "));
+        match &self.derivacoes {
+            Some((sentenca, primeira, segunda)) => {
+                string += format!("  a entrada {} tem duas derivações:\n", sentenca.join(" ")).as_ref();
+                string += format!("    {}\n    {}\n", primeira, segunda).as_ref();
+            }
+            None => {
+                for (acao, exemplo) in self.exemplos.iter() {
+                    match exemplo {
+                        Some((sentenca, marcador)) => {
+                            let mut simbolos = sentenca.to_vec();
+                            simbolos.insert(*marcador, "•".to_string());
+                            string += format!("  exemplo para {}: {}\n", acao, simbolos.join(" ")).as_ref();
+                        }
+                        None => string += format!("  exemplo para {}: nenhuma entrada encontrada\n", acao).as_ref(),
+                    }
+                }
+            }
+        }
+        string
+    }
+}
+
+impl<'a> Simulador<'a> {
+    fn novo(automato: &'a Automato) -> Self {
+        let mut colunas = automato.gramatica.terminais.to_vec();
+        colunas.push(FIM_DA_ENTRADA.to_string());
+        let acoes = (0..automato.estados.len())
+            .map(|estado| {
+                colunas
+                    .iter()
+                    .map(|coluna| {
+                        let candidatas = if coluna == FIM_DA_ENTRADA {
+                            automato.determina_acao_final(estado)
+                        } else {
+                            automato.determina_acao_terminal(estado, coluna)
+                        };
+                        candidatas.iter().map(|(acao, _)| *acao).collect()
+                    })
+                    .collect()
+            })
+            .collect();
+        let menores_sentencas = menores_sentencas(&automato.gramatica, &colunas);
+        Simulador {
+            automato,
+            colunas,
+            acoes,
+            desvios: automato.obtem_desvios(),
+            menores_sentencas,
+        }
+    }
+
+    fn contraexemplo(&self, conflito: &Conflito) -> Contraexemplo {
+        let coluna = self.colunas.iter().position(|c| *c == conflito.simbolo).unwrap();
+
+        let mut exemplos: Vec<(Acao, Option<Exemplo>)> = Vec::new();
+        let mut derivacoes: Option<(Vec<String>, String, String)> = None;
+        let mut testadas: Vec<Vec<usize>> = Vec::new();
+        for (acao, _) in conflito.acoes.iter() {
+            let exemplo = self.busca_exemplo(conflito.estado, coluna, *acao);
+            if let Some((sentenca, _)) = &exemplo {
+                if derivacoes.is_none() && !testadas.contains(sentenca) {
+                    let arvores = self.derivacoes(sentenca);
+                    if arvores.len() > 1 {
+                        derivacoes = Some((self.nomes(sentenca), arvores[0].to_string(), arvores[1].to_string()));
+                    }
+                    testadas.push(sentenca.to_vec());
+                }
+            }
+            exemplos.push((
+                *acao,
+                exemplo.map(|(sentenca, marcador)| (self.nomes(&sentenca), marcador)),
+            ));
+        }
+
+        Contraexemplo {
+            caminho: self.caminho_ate(conflito.estado),
+            exemplos,
+            derivacoes,
+        }
+    }
+
+    // símbolos do menor caminho de I0 até o estado, pelas transições do autômato
+    fn caminho_ate(&self, destino: usize) -> Vec<String> {
+        let mut anteriores: Vec<Option<(usize, String)>> = vec![None; self.desvios.len()];
+        let mut visitados = vec![false; self.desvios.len()];
+        let mut fila: VecDeque<usize> = VecDeque::from([0]);
+        visitados[0] = true;
+        while let Some(estado) = fila.pop_front() {
+            for (simbolo, proximo) in self.desvios[estado].iter() {
+                if !visitados[*proximo] {
+                    visitados[*proximo] = true;
+                    anteriores[*proximo] = Some((estado, simbolo.to_string()));
+                    fila.push_back(*proximo);
+                }
+            }
+        }
+
+        let mut caminho: Vec<String> = Vec::new();
+        let mut estado = destino;
+        while let Some((anterior, simbolo)) = &anteriores[estado] {
+            caminho.insert(0, simbolo.to_string());
+            estado = *anterior;
+        }
+        caminho
+    }
+
+    // entrada aceita em que, no estado do conflito e sob a coluna dada, a ação
+    // escolhida é a forçada. os caminhos de I0 até o estado, sem repetir
+    // estados, são tentados do mais curto ao mais longo: cada um dá a pilha no
+    // conflito e o início da entrada, com cada não terminal trocado pela sua
+    // menor sentença. devolve as colunas dos terminais e a posição do conflito
+    fn busca_exemplo(&self, estado: usize, coluna: usize, forcado: Acao) -> Option<(Vec<usize>, usize)> {
+        let mut configuracoes: usize = 0;
+        let mut fila: VecDeque<Vec<usize>> = VecDeque::from([vec![0]]);
+        while let Some(pilha) = fila.pop_front() {
+            configuracoes += 1;
+            if configuracoes > LIMITE_DE_CONFIGURACOES {
+                return None;
+            }
+
+            let topo = *pilha.last().unwrap();
+            if topo == estado {
+                let inicio = self.sentenca(&self.simbolos_da_pilha(&pilha));
+                if let Some(inicio) = inicio {
+                    if let Some(resto) = self.completa(&pilha, coluna, forcado, &mut configuracoes) {
+                        let marcador = inicio.len();
+                        return Some(([inicio, resto].concat(), marcador));
+                    }
+                }
+            }
+            if pilha.len() < LIMITE_DA_PILHA {
+                for (_, proximo) in self.desvios[topo].iter() {
+                    if !pilha.contains(proximo) {
+                        let mut nova = pilha.to_vec();
+                        nova.push(*proximo);
+                        fila.push_back(nova);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    // continuação da entrada a partir da pilha do conflito, seguindo a ação
+    // forçada sob a coluna do conflito. depois de uma redução, só as reduções
+    // sob a mesma coluna são seguidas, até que o terminal do conflito seja
+    // empilhado; o resto da entrada vem então dos itens da pilha
+    fn completa(&self, pilha: &[usize], coluna: usize, forcado: Acao, configuracoes: &mut usize) -> Option<Vec<usize>> {
+        let mut pendentes: Vec<(Vec<usize>, Acao)> = vec![(pilha.to_vec(), forcado)];
+        let mut visitados: HashSet<Vec<usize>> = HashSet::new();
+        while let Some((pilha, passo)) = pendentes.pop() {
+            *configuracoes += 1;
+            if *configuracoes > LIMITE_DE_CONFIGURACOES {
+                return None;
+            }
+            match passo {
+                Acao::Empilha(destino) if pilha.len() < LIMITE_DA_PILHA => {
+                    let mut nova = pilha;
+                    nova.push(destino);
+                    if let Some(resto) = self.completa_pelos_itens(nova) {
+                        return Some([vec![coluna], resto].concat());
+                    }
+                }
+                Acao::Reduz(regra) => {
+                    if let Some(nova) = self.reduz(&pilha, regra) {
+                        if visitados.insert(nova.to_vec()) {
+                            let topo = *nova.last().unwrap();
+                            for passo in self.acoes[topo][coluna].iter() {
+                                pendentes.push((nova.to_vec(), *passo));
+                            }
+                        }
+                    }
+                }
+                Acao::Aceita => return Some(Vec::new()),
+                _ => {}
+            }
+        }
+        None
+    }
+
+    // menor resto de entrada aceito a partir de uma pilha do autômato. todo
+    // caminho de I0 é um prefixo viável: no topo, um item inicial A -> α.β tem
+    // o α nos últimos estados da pilha, então β pode ser completado com a sua
+    // menor sentença e o item reduzido, até chegar à regra aumentada. entre os
+    // itens, vale o de menor sentença e, no empate, o de maior α
+    fn completa_pelos_itens(&self, pilha: Vec<usize>) -> Option<Vec<usize>> {
+        let regras = &self.automato.gramatica.regras;
+        let mut pilha = pilha;
+        let mut resto: Vec<usize> = Vec::new();
+        for _ in 0..LIMITE_DE_CONFIGURACOES {
+            let topo = *pilha.last().unwrap();
+            let (item, sentenca) = self.automato.estados[topo].itens_iniciais
+                .iter()
+                .filter(|item| item.posicao_do_ponto < pilha.len())
+                .filter_map(|item| {
+                    let sentenca = self.sentenca(&regras[item.producao].producao[item.posicao_do_ponto..])?;
+                    Some((item, sentenca))
+                })
+                .min_by_key(|(item, sentenca)| (sentenca.len(), Reverse(item.posicao_do_ponto)))?;
+            resto.extend(sentenca);
+            if item.producao == 0 {
+                return Some(resto);
+            }
+
+            pilha.truncate(pilha.len() - item.posicao_do_ponto);
+            let nao_terminal = &regras[item.producao].nao_terminal;
+            let (_, destino) = self.desvios[*pilha.last().unwrap()].iter().find(|(simbolo, _)| simbolo == nao_terminal)?;
+            pilha.push(*destino);
+        }
+        None
+    }
+
+    // símbolos pelos quais a pilha de estados foi alcançada a partir de I0
+    fn simbolos_da_pilha(&self, pilha: &[usize]) -> Vec<String> {
+        pilha
+            .windows(2)
+            .map(|par| {
+                let (simbolo, _) = self.desvios[par[0]].iter().find(|(_, destino)| *destino == par[1]).unwrap();
+                simbolo.to_string()
+            })
+            .collect()
+    }
+
+    // colunas dos terminais de uma sequência de símbolos, com cada não
+    // terminal trocado pela sua menor sentença
+    fn sentenca(&self, simbolos: &[String]) -> Option<Vec<usize>> {
+        sentenca(&self.automato.gramatica, &self.colunas, &self.menores_sentencas, simbolos)
+    }
+
+    // árvores de derivação da entrada, no máximo duas, seguindo todas as ações
+    // candidatas; cada sequência de ações aceita é uma derivação mais à direita
+    fn derivacoes(&self, sentenca: &[usize]) -> Vec<String> {
+        let fim = self.colunas.len() - 1;
+        let limite_de_passos = 10 * (sentenca.len() + 1) + 50;
+        let mut arvores: Vec<String> = Vec::new();
+        let mut configuracoes: usize = 0;
+        let mut pendentes: Vec<(Vec<usize>, usize, Historico)> = vec![(vec![0], 0, Vec::new())];
+
+        while let Some((pilha, posicao, historico)) = pendentes.pop() {
+            configuracoes += 1;
+            if arvores.len() > 1 || configuracoes > LIMITE_DE_CONFIGURACOES {
+                break;
+            }
+            if historico.len() > limite_de_passos {
+                continue;
+            }
+            let c = sentenca.get(posicao).copied().unwrap_or(fim);
+            for passo in self.acoes[*pilha.last().unwrap()][c].iter().rev() {
+                let mut historico = historico.to_vec();
+                historico.push((*passo, c));
+                match passo {
+                    Acao::Empilha(destino) if pilha.len() < LIMITE_DA_PILHA => {
+                        let mut nova = pilha.to_vec();
+                        nova.push(*destino);
+                        pendentes.push((nova, posicao + 1, historico));
+                    }
+                    Acao::Reduz(regra) => {
+                        if let Some(nova) = self.reduz(&pilha, *regra) {
+                            pendentes.push((nova, posicao, historico));
+                        }
+                    }
+                    Acao::Aceita => arvores.push(self.arvore(&historico)),
+                    _ => {}
+                }
+            }
+        }
+        arvores
+    }
+
+    // pilha depois da redução, ou None se ela não cabe. as reduções por regras
+    // vazias fazem a pilha crescer sem ler entrada, então o limite vale para elas
+    // como para os empilhamentos
+    fn reduz(&self, pilha: &[usize], regra: usize) -> Option<Vec<usize>> {
+        let regra = &self.automato.gramatica.regras[regra];
+        if regra.producao.len() >= pilha.len() || (regra.producao.is_empty() && pilha.len() >= LIMITE_DA_PILHA) {
+            return None;
+        }
+        let mut nova = pilha[..pilha.len() - regra.producao.len()].to_vec();
+        let (_, destino) = self.desvios[*nova.last().unwrap()]
+            .iter()
+            .find(|(simbolo, _)| *simbolo == regra.nao_terminal)?;
+        nova.push(*destino);
+        Some(nova)
+    }
+
+    // árvore no formato E[ E[ Id ] Mais E[ Id ] ], montada a partir das ações
+    fn arvore(&self, historico: &[(Acao, usize)]) -> String {
+        let mut nos: Vec<String> = Vec::new();
+        for (passo, coluna) in historico {
+            match passo {
+                Acao::Empilha(_) => nos.push(self.colunas[*coluna].to_string()),
+                Acao::Reduz(regra) => {
+                    let regra = &self.automato.gramatica.regras[*regra];
+                    let filhos = nos.split_off(nos.len() - regra.producao.len());
+                    let filhos = if filhos.is_empty() { "ε".to_string() } else { filhos.join(" ") };
+                    nos.push(format!("{}[ {} ]", regra.nao_terminal, filhos));
+                }
+                _ => {}
+            }
+        }
+        nos.pop().unwrap_or_default()
+    }
+
+    fn nomes(&self, sentenca: &[usize]) -> Vec<String> {
+        sentenca.iter().map(|c| self.colunas[*c].to_string()).collect()
+    }
+}
+
+// menor sentença de cada não terminal, calculada como o NULLABLE: as regras são
+// percorridas até nenhuma sentença encurtar
+fn menores_sentencas(gramatica: &Gramatica, colunas: &[String]) -> Vec<Option<Vec<usize>>> {
+    let mut menores: Vec<Option<Vec<usize>>> = vec![None; gramatica.nao_terminais.len()];
+    let mut mudou = true;
+    while mudou {
+        mudou = false;
+        for regra in gramatica.regras.iter() {
+            let Some(nova) = sentenca(gramatica, colunas, &menores, &regra.producao) else {
+                continue;
+            };
+            let indice = gramatica.nao_terminais.iter().position(|n| *n == regra.nao_terminal).unwrap();
+            if menores[indice].as_ref().is_none_or(|menor| nova.len() < menor.len()) {
+                menores[indice] = Some(nova);
+                mudou = true;
+            }
+        }
+    }
+    menores
+}
+
+fn sentenca(
+    gramatica: &Gramatica,
+    colunas: &[String],
+    menores: &[Option<Vec<usize>>],
+    simbolos: &[String],
+) -> Option<Vec<usize>> {
+    let mut sentenca: Vec<usize> = Vec::new();
+    for simbolo in simbolos {
+        match colunas.iter().position(|c| c == simbolo) {
+            Some(coluna) => sentenca.push(coluna),
+            None => {
+                let indice = gramatica.nao_terminais.iter().position(|n| n == simbolo)?;
+                sentenca.extend(menores[indice].as_ref()?);
+            }
+        }
+    }
+    Some(sentenca)
+}
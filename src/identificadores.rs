@@ -0,0 +1,169 @@
+use crate::leitor::sem_aspas;
+use crate::{Gramatica, FIM_DA_ENTRADA, INICIO_AUMENTADO};
+
+// variante de Tokens reservada para o fim da entrada
+const FIM: &str = "Fim";
+
+// nomes dos símbolos da gramática no código Rust gerado, nas mesmas posições
+// de gramatica.terminais e gramatica.nao_terminais
+#[derive(Debug, Clone)]
+pub struct Identificadores {
+    pub terminais: Vec<String>,
+    // se o terminal carrega um valor, escrito na gramática como Id(_); os
+    // padrões do trecho para o liac são então Tokens::Id(_)
+    pub com_dados: Vec<bool>,
+    pub nao_terminais: Vec<String>,
+}
+
+impl Gramatica {
+    // identificador de cada símbolo, ou a lista de colisões: dois símbolos com
+    // o mesmo identificador no mesmo enum, ou um terminal chamado como o Fim
+    pub fn identificadores(&self) -> Result<Identificadores, Vec<String>> {
+        let terminais: Vec<String> = self.terminais.iter().map(|t| identificador(t)).collect();
+        let com_dados = self.terminais.iter().map(|t| separa_dados(t).1).collect();
+        // o não terminal da regra aumentada é o inicial com uma linha: S' vira SL
+        let nao_terminais: Vec<String> = self.nao_terminais
+            .iter()
+            .map(|n| match n.as_str() {
+                INICIO_AUMENTADO => identificador(&format!("{}'", self.regras[0].producao[0])),
+                _ => identificador(n),
+            })
+            .collect();
+
+        let mut colisoes: Vec<String> = Vec::new();
+        for (i, nome) in terminais.iter().enumerate() {
+            if nome == FIM {
+                colisoes.push(format!(
+                    "o terminal {} gera o identificador Tokens::{}, reservado para o fim da entrada",
+                    self.terminais[i], FIM,
+                ));
+            } else if let Some(j) = terminais[..i].iter().position(|outro| outro == nome) {
+                colisoes.push(format!(
+                    "os terminais {} e {} geram o mesmo identificador Tokens::{}",
+                    self.terminais[j], self.terminais[i], nome,
+                ));
+            }
+        }
+        for (i, nome) in nao_terminais.iter().enumerate() {
+            if let Some(j) = nao_terminais[..i].iter().position(|outro| outro == nome) {
+                colisoes.push(format!(
+                    "os não terminais {} e {} geram o mesmo identificador NaoTerminais::{}",
+                    self.nao_terminais[j], self.nao_terminais[i], nome,
+                ));
+            }
+        }
+        if !colisoes.is_empty() {
+            return Err(colisoes);
+        }
+
+        Ok(Identificadores {
+            terminais,
+            com_dados,
+            nao_terminais,
+        })
+    }
+}
+
+impl Identificadores {
+    // padrão do terminal no trecho para o liac, como Tokens::Id ou Tokens::Id(_)
+    pub fn padrao_do_terminal(&self, terminal: usize) -> String {
+        if self.com_dados[terminal] {
+            format!("Tokens::{}(_)", self.terminais[terminal])
+        } else {
+            format!("Tokens::{}", self.terminais[terminal])
+        }
+    }
+
+    // tabela de cada símbolo da gramática para o seu nome no código gerado
+    pub fn formata_md(&self, gramatica: &Gramatica) -> String {
+        let mut string = "| Símbolo | Identificador |\n|---|---|\n".to_string();
+        for (i, terminal) in gramatica.terminais.iter().enumerate() {
+            string += format!("| {} | {} |\n", terminal, self.padrao_do_terminal(i)).as_ref();
+        }
+        string += format!("| {} | Tokens::{} |\n", FIM_DA_ENTRADA, FIM).as_ref();
+        for (i, nao_terminal) in gramatica.nao_terminais.iter().enumerate() {
+            string += format!("| {} | NaoTerminais::{} |\n", nao_terminal, self.nao_terminais[i]).as_ref();
+        }
+        string
+    }
+}
+
+// separa um valor no fim do símbolo, como em IdDeVariavel(_), do nome
+fn separa_dados(simbolo: &str) -> (&str, bool) {
+    match simbolo.find('(') {
+        Some(inicio) if inicio > 0 && simbolo.ends_with(')') => (&simbolo[..inicio], true),
+        _ => (simbolo, false),
+    }
+}
+
+// identificador em CamelCase: literais entre aspas perdem as aspas, letras e
+// dígitos ASCII e _ são mantidos, a pontuação vira o nome do caractere e o
+// resto, inclusive letras acentuadas e sobrescritos como ², o código Unicode.
+// nomes que não começam por letra e o Self ganham o prefixo Simbolo. como todo
+// identificador começa por maiúscula, as demais palavras reservadas, todas
+// minúsculas, não aparecem
+fn identificador(simbolo: &str) -> String {
+    let (nome, _) = separa_dados(simbolo);
+    let nome = sem_aspas(nome).unwrap_or(nome);
+
+    let mut string = String::new();
+    for c in nome.chars() {
+        if c.is_ascii_alphanumeric() || c == '_' {
+            string.push(c);
+        } else {
+            match nome_do_caractere(c) {
+                Some(nome) => string += nome,
+                None => string += format!("U{:04X}", c as u32).as_ref(),
+            }
+        }
+    }
+
+    let mut caracteres = string.chars();
+    let string = match caracteres.next() {
+        Some(primeiro) => primeiro.to_uppercase().chain(caracteres).collect(),
+        None => string,
+    };
+    if !string.starts_with(|c: char| c.is_ascii_alphabetic()) || string == "Self" {
+        format!("Simbolo{}", string)
+    } else {
+        string
+    }
+}
+
+fn nome_do_caractere(c: char) -> Option<&'static str> {
+    Some(match c {
+        // linha, como em S'
+        '\'' => "L",
+        '+' => "Mais",
+        '-' => "Menos",
+        '*' => "Asterisco",
+        '/' => "Barra",
+        '\\' => "BarraInvertida",
+        '(' => "AbreP",
+        ')' => "FechaP",
+        '[' => "AbreColchete",
+        ']' => "FechaColchete",
+        '{' => "AbreChave",
+        '}' => "FechaChave",
+        '<' => "Menor",
+        '>' => "Maior",
+        '=' => "Igual",
+        '!' => "Exclamacao",
+        '?' => "Interrogacao",
+        ',' => "Virgula",
+        ';' => "PontoEVirgula",
+        ':' => "DoisPontos",
+        '.' => "Ponto",
+        '&' => "EComercial",
+        '|' => "BarraVertical",
+        '^' => "Circunflexo",
+        '%' => "Porcento",
+        '#' => "Cerquilha",
+        '@' => "Arroba",
+        '~' => "Til",
+        '"' => "Aspas",
+        '`' => "Crase",
+        '$' => "Cifrao",
+        _ => return None,
+    })
+}
@@ -0,0 +1,753 @@
+use crate::padrao::Padrao;
+use crate::RegraDeProducao;
+
+// associatividade declarada por %left, %right ou %nonassoc
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Associatividade {
+    Esquerda,
+    Direita,
+    NaoAssociativa,
+}
+
+// marcadores aceitos para uma produção vazia, como em A -> ε
+const MARCADORES_DE_VAZIO: [&str; 2] = ["ε", "%empty"];
+
+// nome do não terminal da regra aumentada inserida pelo leitor. símbolos
+// iniciados por $ são reservados, então ele não colide com os da gramática
+pub const INICIO_AUMENTADO: &str = "$inicio";
+
+// operadores EBNF: opcional, repetição (zero ou mais) e repetição (um ou mais)
+const OPERADORES: [char; 3] = ['?', '*', '+'];
+
+// elemento do lado direito de uma regra antes da expansão dos operadores EBNF
+#[derive(Debug, Clone)]
+enum Elemento {
+    Simbolo(String, Option<char>),
+    Grupo(Vec<Vec<Elemento>>, Option<char>),
+    // %prec X, com a coluna do %prec; retirado da alternativa antes da expansão
+    Precedencia(String, usize),
+}
+
+// erro de leitura da gramática, também usado para os avisos; linha e coluna
+// começam em 1
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErroDeLeitura {
+    pub linha: usize,
+    pub coluna: usize,
+    pub mensagem: String,
+}
+
+// símbolo com a linha e a coluna em que aparece no arquivo
+type SimboloNaLinha = (String, usize, usize);
+
+// alternativa lida do arquivo, antes da expansão dos operadores EBNF
+struct RegraLida {
+    nao_terminal: String,
+    elementos: Vec<Elemento>,
+    // símbolo do %prec da alternativa, com a posição em que aparece
+    precedencia: Option<SimboloNaLinha>,
+    linha: usize,
+}
+
+// símbolo do lado direito com a coluna em que começa na linha
+#[derive(Debug, Clone)]
+struct Token {
+    texto: String,
+    coluna: usize,
+}
+
+// regras lidas do arquivo e os avisos que não impedem a leitura
+pub struct Leitura {
+    pub regras: Vec<RegraDeProducao>,
+    // terminais com precedência declarada, o nível (maior liga mais forte) e a
+    // associatividade
+    pub precedencias: Vec<(String, usize, Associatividade)>,
+    pub tokens_declarados: Option<Vec<String>>,
+    pub avisos: Vec<ErroDeLeitura>,
+}
+
+pub fn obtem_regras_de_producao(linhas_arquivo: Vec<&str>) -> Result<Leitura, Vec<ErroDeLeitura>> {
+    let mut regras_lidas: Vec<RegraLida> = Vec::new();
+    let mut erros: Vec<ErroDeLeitura> = Vec::new();
+    let mut avisos: Vec<ErroDeLeitura> = Vec::new();
+    // símbolos do lado direito com a linha e a coluna em que aparecem
+    let mut ocorrencias: Vec<SimboloNaLinha> = Vec::new();
+    // tokens declarados por %token, com a linha e a coluna da declaração;
+    // None quando não há nenhuma seção %token
+    let mut tokens_declarados: Option<Vec<SimboloNaLinha>> = None;
+    // símbolo inicial declarado por %start, com a linha e a coluna da declaração
+    let mut simbolo_declarado: Option<SimboloNaLinha> = None;
+    // precedências declaradas, com a linha e a coluna de cada símbolo; cada
+    // linha %left, %right ou %nonassoc é um nível acima da anterior
+    let mut precedencias: Vec<(String, usize, Associatividade, usize, usize)> = Vec::new();
+    let mut nivel: usize = 0;
+    // convenção de nomes dos não terminais declarada por %nonterminals
+    let mut padrao_de_nao_terminais: Option<Padrao> = None;
+
+    // lê linha por linha para obter a gramática
+    for (indice, linha) in linhas_arquivo.into_iter().enumerate() {
+        let numero = indice + 1;
+        let linha = remove_comentario(linha);
+        let erro = |coluna: usize, mensagem: &str| ErroDeLeitura {
+            linha: numero,
+            coluna,
+            mensagem: mensagem.to_string(),
+        };
+
+        // linhas em branco ou só com comentário são ignoradas
+        if linha.trim().is_empty() {
+            continue;
+        }
+
+        // diretivas
+        if linha.trim_start().starts_with('%') {
+            let palavras: Vec<&str> = linha.split_whitespace().collect();
+            let posicao_de = |palavra: &str| coluna(linha, palavra.as_ptr() as usize - linha.as_ptr() as usize);
+            match palavras[0] {
+                "%start" => {
+                    if palavras.len() != 2 {
+                        let posicao = palavras.get(2).map(|p| posicao_de(p)).unwrap_or(linha.trim_end().chars().count() + 1);
+                        erros.push(erro(posicao, "esperado exatamente um símbolo após %start"));
+                    } else if simbolo_declarado.is_some() {
+                        erros.push(erro(posicao_de(palavras[0]), "esperado um único %start na gramática"));
+                    } else {
+                        simbolo_declarado = Some((palavras[1].to_string(), numero, posicao_de(palavras[1])));
+                    }
+                }
+                "%nonterminals" => {
+                    if palavras.len() != 2 {
+                        let posicao = palavras.get(2).map(|p| posicao_de(p)).unwrap_or(linha.trim_end().chars().count() + 1);
+                        erros.push(erro(posicao, "esperado exatamente um padrão após %nonterminals"));
+                    } else if padrao_de_nao_terminais.is_some() {
+                        erros.push(erro(posicao_de(palavras[0]), "esperado um único %nonterminals na gramática"));
+                    } else {
+                        match Padrao::novo(palavras[1]) {
+                            Ok(padrao) => padrao_de_nao_terminais = Some(padrao),
+                            Err(mensagem) => erros.push(erro(posicao_de(palavras[1]), &format!("padrão inválido: {}", mensagem))),
+                        }
+                    }
+                }
+                "%token" => {
+                    if palavras.len() == 1 {
+                        erros.push(erro(linha.trim_end().chars().count() + 1, "esperado ao menos um símbolo após %token"));
+                    }
+                    let declarados = tokens_declarados.get_or_insert_with(Vec::new);
+                    for palavra in &palavras[1..] {
+                        if palavra.starts_with('$') {
+                            erros.push(erro(posicao_de(palavra), "esperado um símbolo, símbolos iniciados por $ são reservados"));
+                        } else if !declarados.iter().any(|(t, _, _)| t == palavra) {
+                            declarados.push((palavra.to_string(), numero, posicao_de(palavra)));
+                        }
+                    }
+                }
+                "%left" | "%right" | "%nonassoc" => {
+                    let associatividade = match palavras[0] {
+                        "%left" => Associatividade::Esquerda,
+                        "%right" => Associatividade::Direita,
+                        _ => Associatividade::NaoAssociativa,
+                    };
+                    if palavras.len() == 1 {
+                        erros.push(erro(linha.trim_end().chars().count() + 1, &format!("esperado ao menos um símbolo após {}", palavras[0])));
+                    }
+                    nivel += 1;
+                    for palavra in &palavras[1..] {
+                        if palavra.starts_with('$') {
+                            erros.push(erro(posicao_de(palavra), "esperado um símbolo, símbolos iniciados por $ são reservados"));
+                        } else if precedencias.iter().any(|p| p.0 == *palavra) {
+                            erros.push(erro(posicao_de(palavra), &format!("esperado um símbolo sem precedência, {} já foi declarado", palavra)));
+                        } else {
+                            precedencias.push((palavra.to_string(), nivel, associatividade, numero, posicao_de(palavra)));
+                        }
+                    }
+                }
+                diretiva => {
+                    erros.push(erro(
+                        posicao_de(diretiva),
+                        &format!("diretiva desconhecida {}, esperado %start, %token, %nonterminals, %left, %right ou %nonassoc", diretiva),
+                    ));
+                }
+            }
+            continue;
+        }
+
+        let (nao_terminal, alternativas, inicio) = match linha.trim_start().strip_prefix('|') {
+            // linha de continuação: novas alternativas para o último não terminal
+            Some(resto) => {
+                let inicio = linha.len() - resto.len();
+                match regras_lidas.last() {
+                    Some(anterior) => (anterior.nao_terminal.to_string(), resto, inicio),
+                    None => {
+                        erros.push(erro(coluna(linha, inicio - 1), "esperada uma regra A -> α antes da linha de continuação iniciada por |"));
+                        continue;
+                    }
+                }
+            }
+            None => {
+                let (nao_terminal, alternativas) = match linha.split_once("->") {
+                    Some(partes) => partes,
+                    None => {
+                        let sem_recuo = linha.trim_start();
+                        let posicao = linha.len() - sem_recuo.len()
+                            + sem_recuo.find(char::is_whitespace).unwrap_or(sem_recuo.len());
+                        erros.push(erro(coluna(linha, posicao), "esperado -> após o não terminal"));
+                        continue;
+                    }
+                };
+                let simbolos = separa_tokens(linha, 0, nao_terminal);
+                if simbolos.is_empty() {
+                    erros.push(erro(1, "esperado um não terminal antes de ->"));
+                    continue;
+                }
+                if simbolos.len() > 1 {
+                    erros.push(erro(simbolos[1].coluna, "esperado -> após o não terminal, encontrado outro símbolo"));
+                    continue;
+                }
+                if simbolos[0].texto.starts_with('$') {
+                    erros.push(erro(simbolos[0].coluna, "esperado um não terminal, símbolos iniciados por $ são reservados"));
+                    continue;
+                }
+                (simbolos[0].texto.to_string(), alternativas, nao_terminal.len() + 2)
+            }
+        };
+
+        // cada alternativa separada por | é uma regra de produção própria,
+        // numerada na ordem em que aparece
+        let tokens = separa_tokens(linha, inicio, alternativas);
+        let mut erros_da_linha: Vec<ErroDeLeitura> = Vec::new();
+        if let Some(token) = tokens.iter().find(|t| t.texto.contains("->") && sem_aspas(&t.texto).is_none()) {
+            erros_da_linha.push(erro(token.coluna, "esperado um símbolo, encontrado um segundo ->"));
+        }
+        if let Some(token) = tokens.iter().find(|t| t.texto.starts_with('$')) {
+            erros_da_linha.push(erro(token.coluna, "esperado um símbolo, símbolos iniciados por $ são reservados"));
+        }
+        for (i, token) in tokens.iter().enumerate() {
+            if token.texto == "%prec" {
+                match tokens.get(i + 1) {
+                    Some(seguinte) if seguinte.texto != "|" && seguinte.texto != "(" && !fecha_grupo(&seguinte.texto) => {}
+                    _ => erros_da_linha.push(erro(token.coluna, "esperado um símbolo após %prec")),
+                }
+            }
+        }
+        let grupos = obtem_grupos(&tokens, &mut erros_da_linha, numero);
+        if !erros_da_linha.is_empty() {
+            erros.append(&mut erros_da_linha);
+            continue;
+        }
+        let mut simbolos: Vec<(String, usize)> = Vec::new();
+        let coluna_final = linha.trim_end().chars().count() + 1;
+        let alternativas = analisa_alternativas(&tokens, 0, tokens.len(), &grupos, &mut simbolos, &mut erros, (numero, coluna_final));
+        for mut alternativa in alternativas {
+            // o %prec vale para a alternativa inteira, então não pode estar
+            // dentro de um grupo nem aparecer duas vezes
+            let mut marcadas: Vec<(String, usize)> = Vec::new();
+            alternativa.retain(|elemento| match elemento {
+                Elemento::Precedencia(simbolo, coluna) => {
+                    marcadas.push((simbolo.to_string(), *coluna));
+                    false
+                }
+                _ => true,
+            });
+            if let Some(coluna) = precedencia_em_grupo(&alternativa) {
+                erros.push(erro(coluna, "esperado %prec fora de grupos, ele vale para a alternativa inteira"));
+            }
+            if let Some((_, coluna)) = marcadas.get(1) {
+                erros.push(erro(*coluna, "esperado um único %prec por alternativa"));
+            }
+            let precedencia = marcadas.into_iter().next().map(|(simbolo, coluna)| (simbolo, numero, coluna));
+            regras_lidas.push(RegraLida {
+                nao_terminal: nao_terminal.to_string(),
+                elementos: alternativa,
+                precedencia,
+                linha: numero,
+            });
+        }
+        ocorrencias.extend(simbolos.into_iter().map(|(simbolo, coluna)| (simbolo, numero, coluna)));
+    }
+
+    // um símbolo que segue a convenção de nomes dos não terminais mas não tem
+    // nenhuma regra provavelmente é um não terminal esquecido, e não um terminal,
+    // a menos que tenha sido declarado por %token
+    if let Some(padrao) = &padrao_de_nao_terminais {
+        for (simbolo, linha, coluna) in ocorrencias.iter() {
+            let declarado = tokens_declarados.as_ref().is_some_and(|d| d.iter().any(|(t, _, _)| t == simbolo));
+            if padrao.casa(simbolo) && !declarado && !regras_lidas.iter().any(|r| r.nao_terminal == *simbolo) {
+                erros.push(ErroDeLeitura {
+                    linha: *linha,
+                    coluna: *coluna,
+                    mensagem: format!("esperada ao menos uma regra para {}, que segue a convenção de nomes dos não terminais", simbolo),
+                });
+            }
+        }
+    }
+
+    // com uma seção %token, todo símbolo do lado direito precisa ser um não
+    // terminal ou um token declarado, o que pega erros de digitação
+    if let Some(declarados) = &tokens_declarados {
+        let tem_regras = |simbolo: &str| regras_lidas.iter().any(|r| r.nao_terminal == simbolo);
+        for (simbolo, linha, coluna) in ocorrencias.iter() {
+            // símbolos com precedência declarada também são tokens, e os que
+            // seguem a convenção dos não terminais já foram relatados acima
+            if !tem_regras(simbolo) &&
+                !declarados.iter().any(|(t, _, _)| t == simbolo) &&
+                !precedencias.iter().any(|p| p.0 == *simbolo) &&
+                !padrao_de_nao_terminais.as_ref().is_some_and(|p| p.casa(simbolo))
+            {
+                erros.push(ErroDeLeitura {
+                    linha: *linha,
+                    coluna: *coluna,
+                    mensagem: format!("esperado um não terminal ou um token declarado por %token, {} não foi declarado", simbolo),
+                });
+            }
+        }
+        for (token, linha, coluna) in declarados.iter() {
+            if tem_regras(token) {
+                erros.push(ErroDeLeitura {
+                    linha: *linha,
+                    coluna: *coluna,
+                    mensagem: format!("esperado um terminal após %token, {} tem regras de produção", token),
+                });
+            } else if !ocorrencias.iter().any(|(simbolo, _, _)| simbolo == token) {
+                avisos.push(ErroDeLeitura {
+                    linha: *linha,
+                    coluna: *coluna,
+                    mensagem: format!("token {} declarado mas não usado em nenhuma regra", token),
+                });
+            }
+        }
+    }
+
+    // precedências só valem para terminais, e o %prec precisa de um símbolo
+    // com precedência declarada
+    for (simbolo, _, _, linha, coluna) in precedencias.iter() {
+        if regras_lidas.iter().any(|r| r.nao_terminal == *simbolo) {
+            erros.push(ErroDeLeitura {
+                linha: *linha,
+                coluna: *coluna,
+                mensagem: format!("esperado um terminal, {} tem regras de produção", simbolo),
+            });
+        }
+    }
+    for regra in regras_lidas.iter() {
+        if let Some((simbolo, linha, coluna)) = &regra.precedencia {
+            if !precedencias.iter().any(|p| p.0 == *simbolo) {
+                erros.push(ErroDeLeitura {
+                    linha: *linha,
+                    coluna: *coluna,
+                    mensagem: format!("esperado após %prec um símbolo declarado por %left, %right ou %nonassoc, {} não tem precedência", simbolo),
+                });
+            }
+        }
+    }
+
+    if regras_lidas.is_empty() && erros.is_empty() {
+        erros.push(ErroDeLeitura {
+            linha: 1,
+            coluna: 1,
+            mensagem: "esperada ao menos uma regra de produção".to_string(),
+        });
+    }
+
+    // o símbolo inicial é o declarado por %start ou, na falta dele, o lado
+    // esquerdo da primeira regra
+    let simbolo_inicial = match simbolo_declarado {
+        Some((simbolo, linha, coluna)) => {
+            if !regras_lidas.iter().any(|r| r.nao_terminal == simbolo) {
+                erros.push(ErroDeLeitura {
+                    linha,
+                    coluna,
+                    mensagem: format!("esperado um não terminal com regras após %start, {} não tem nenhuma", simbolo),
+                });
+            }
+            simbolo
+        }
+        None => regras_lidas.first().map(|r| r.nao_terminal.to_string()).unwrap_or_default(),
+    };
+    if !erros.is_empty() {
+        return Err(erros);
+    }
+
+    // os não terminais auxiliares dos operadores EBNF não podem colidir com
+    // nenhum símbolo já usado na gramática
+    let mut expansor = Expansor {
+        nomes_usados: Vec::new(),
+        auxiliares: Vec::new(),
+        linha: 0,
+    };
+    for regra in regras_lidas.iter() {
+        expansor.nomes_usados.push(regra.nao_terminal.to_string());
+        coleta_simbolos(&regra.elementos, &mut expansor.nomes_usados);
+    }
+
+    // a regra aumentada $inicio -> S é sempre a regra 0, de modo que a i-ésima
+    // regra escrita é a regra i; as regras auxiliares vão para o fim,
+    // preservando a numeração das regras escritas
+    let mut regras_de_producao: Vec<RegraDeProducao> = vec![RegraDeProducao {
+        nao_terminal: INICIO_AUMENTADO.to_string(),
+        producao: vec![simbolo_inicial],
+        precedencia: None,
+        linha: 0,
+    }];
+    for regra in regras_lidas {
+        expansor.linha = regra.linha;
+        let producao = expansor.expande(&regra.nao_terminal, regra.elementos);
+        regras_de_producao.push(RegraDeProducao {
+            nao_terminal: regra.nao_terminal,
+            producao,
+            precedencia: regra.precedencia.map(|(simbolo, _, _)| simbolo),
+            linha: regra.linha,
+        });
+    }
+    regras_de_producao.append(&mut expansor.auxiliares);
+
+    Ok(Leitura {
+        regras: regras_de_producao,
+        precedencias: precedencias
+            .into_iter()
+            .map(|(simbolo, nivel, associatividade, _, _)| (simbolo, nivel, associatividade))
+            .collect(),
+        tokens_declarados: tokens_declarados.map(|declarados| declarados.into_iter().map(|(token, _, _)| token).collect()),
+        avisos,
+    })
+}
+
+// formata o erro no estilo arquivo:linha:coluna, com a linha e um ^ sob a coluna
+pub fn formata_erro(arquivo: &str, linhas_arquivo: &[&str], erro: &ErroDeLeitura) -> String {
+    formata(arquivo, linhas_arquivo, erro, "erro")
+}
+
+pub fn formata_aviso(arquivo: &str, linhas_arquivo: &[&str], aviso: &ErroDeLeitura) -> String {
+    formata(arquivo, linhas_arquivo, aviso, "aviso")
+}
+
+fn formata(arquivo: &str, linhas_arquivo: &[&str], erro: &ErroDeLeitura, tipo: &str) -> String {
+    let linha = linhas_arquivo.get(erro.linha - 1).copied().unwrap_or("").trim_end_matches('\r');
+    let numero = erro.linha.to_string();
+    let margem = " ".repeat(numero.len());
+    // tabulações são mantidas para que o ^ fique alinhado com o texto
+    let recuo: String = linha
+        .chars()
+        .take(erro.coluna - 1)
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+    format!(
+        "{}:{}:{}: {}: {}\n{} |\n{} | {}\n{} | {}^",
+        arquivo, erro.linha, erro.coluna, tipo, erro.mensagem,
+        margem,
+        numero, linha,
+        margem, recuo,
+    )
+}
+
+// remove o \r de finais de linha CRLF e o comentário iniciado por # ou //, que
+// só começa no início da linha ou após um espaço para não cortar símbolos
+fn remove_comentario(linha: &str) -> &str {
+    let linha = linha.strip_suffix('\r').unwrap_or(linha);
+    let mut anterior = ' ';
+    for (i, c) in linha.char_indices() {
+        if anterior.is_whitespace() && (c == '#' || linha[i..].starts_with("//")) {
+            return &linha[..i];
+        }
+        anterior = c;
+    }
+    linha
+}
+
+// coluna, contada em caracteres a partir de 1, da posição em bytes na linha
+fn coluna(linha: &str, posicao: usize) -> usize {
+    linha[..posicao].chars().count() + 1
+}
+
+// separa o lado direito, que começa na posição inicio da linha, em símbolos
+// pelos espaços; | é um token próprio, exceto dentro de aspas, como em '|'
+fn separa_tokens(linha: &str, inicio: usize, texto: &str) -> Vec<Token> {
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut posicao_da_palavra: Option<usize> = None;
+    for (i, c) in texto.char_indices().chain(std::iter::once((texto.len(), ' '))) {
+        let entre_aspas = posicao_da_palavra.is_some_and(|p| {
+            let palavra = &texto[p..i];
+            palavra.starts_with(['\'', '"']) && (palavra.len() == 1 || !palavra.ends_with(&palavra[..1]))
+        });
+        if c.is_whitespace() || (c == '|' && !entre_aspas) {
+            if let Some(p) = posicao_da_palavra.take() {
+                tokens.push(Token {
+                    texto: texto[p..i].to_string(),
+                    coluna: coluna(linha, inicio + p),
+                });
+            }
+            if c == '|' {
+                tokens.push(Token {
+                    texto: "|".to_string(),
+                    coluna: coluna(linha, inicio + i),
+                });
+            }
+        } else if posicao_da_palavra.is_none() {
+            posicao_da_palavra = Some(i);
+        }
+    }
+    tokens
+}
+
+// literal entre aspas simples ou duplas, como em '+', sem as aspas. um literal
+// nunca é operador, marcador, grupo ou separador de alternativas
+pub(crate) fn sem_aspas(token: &str) -> Option<&str> {
+    match token.chars().next() {
+        Some(aspas @ ('\'' | '"')) if token.len() > 2 && token.ends_with(aspas) => Some(&token[1..token.len() - 1]),
+        _ => None,
+    }
+}
+
+// separa o operador EBNF do fim do token, como em Id+. o que sobra precisa ser
+// um símbolo, não vazio e sem outro operador no fim, então ++ e C++ são
+// símbolos; um literal entre aspas termina em aspas e nunca tem operador
+fn separa_operador(token: &str) -> (&str, Option<char>) {
+    match token.chars().last() {
+        Some(operador) if OPERADORES.contains(&operador) => {
+            let simbolo = &token[..token.len() - 1];
+            match simbolo.chars().last() {
+                Some(ultimo) if !OPERADORES.contains(&ultimo) => (simbolo, Some(operador)),
+                _ => (token, None),
+            }
+        }
+        _ => (token, None),
+    }
+}
+
+fn fecha_grupo(token: &str) -> bool {
+    token == ")" || (token.len() == 2 && token.starts_with(')') && token.ends_with(&OPERADORES[..]))
+}
+
+// pares (abertura, fechamento) dos parênteses que formam grupos EBNF. um par só
+// é grupo se o fechamento tiver operador ou se houver | dentro dele; os demais,
+// como em F -> ( T ), continuam sendo terminais
+fn obtem_grupos(tokens: &[Token], erros: &mut Vec<ErroDeLeitura>, linha: usize) -> Vec<(usize, usize)> {
+    let mut grupos: Vec<(usize, usize)> = Vec::new();
+    let mut abertos: Vec<(usize, bool)> = Vec::new();
+    for (i, token) in tokens.iter().enumerate() {
+        if token.texto == "(" {
+            abertos.push((i, false));
+        } else if token.texto == "|" {
+            if let Some(aberto) = abertos.last_mut() {
+                aberto.1 = true;
+            }
+        } else if fecha_grupo(&token.texto) {
+            match abertos.pop() {
+                Some((abertura, tem_alternativas)) if tem_alternativas || token.texto.len() == 2 => {
+                    if abertura + 1 == i {
+                        erros.push(ErroDeLeitura {
+                            linha,
+                            coluna: tokens[abertura].coluna,
+                            mensagem: "esperado ao menos um símbolo dentro do grupo".to_string(),
+                        });
+                    }
+                    grupos.push((abertura, i));
+                }
+                None if token.texto.len() == 2 => erros.push(ErroDeLeitura {
+                    linha,
+                    coluna: token.coluna,
+                    mensagem: format!("esperado ( antes de {}", token.texto),
+                }),
+                _ => {}
+            }
+        }
+    }
+    grupos
+}
+
+// separa as alternativas do trecho tokens[inicio..fim], guardando em simbolos
+// cada símbolo encontrado com a sua coluna. uma alternativa vazia precisa do
+// marcador, e o marcador não pode estar junto de outros símbolos; coluna_final
+// é a coluna logo após o fim da linha
+fn analisa_alternativas(
+    tokens: &[Token],
+    inicio: usize,
+    fim: usize,
+    grupos: &[(usize, usize)],
+    simbolos: &mut Vec<(String, usize)>,
+    erros: &mut Vec<ErroDeLeitura>,
+    (linha, coluna_final): (usize, usize),
+) -> Vec<Vec<Elemento>> {
+    let mut alternativas: Vec<Vec<Elemento>> = vec![Vec::new()];
+    // coluna do marcador de vazio da alternativa atual
+    let mut marcador: Option<usize> = None;
+    let mut i = inicio;
+    while i < fim {
+        let token = &tokens[i].texto;
+        if let Some((_, fechamento)) = grupos.iter().find(|(abertura, _)| *abertura == i) {
+            let internas = analisa_alternativas(tokens, i + 1, *fechamento, grupos, simbolos, erros, (linha, coluna_final));
+            let operador = tokens[*fechamento].texto.chars().nth(1);
+            alternativas.last_mut().unwrap().push(Elemento::Grupo(internas, operador));
+            i = *fechamento + 1;
+            continue;
+        }
+
+        if token == "|" {
+            verifica_vazio(alternativas.last().unwrap(), marcador.take(), tokens[i].coluna, erros, linha);
+            alternativas.push(Vec::new());
+        } else if MARCADORES_DE_VAZIO.contains(&token.as_str()) {
+            marcador = Some(tokens[i].coluna);
+        } else if token == "%prec" {
+            // o símbolo seguinte já foi verificado na leitura da linha
+            let simbolo = tokens[i + 1].texto.to_string();
+            alternativas.last_mut().unwrap().push(Elemento::Precedencia(simbolo, tokens[i].coluna));
+            i += 1;
+        } else {
+            let (simbolo, operador) = separa_operador(token);
+            let elemento = Elemento::Simbolo(simbolo.to_string(), operador);
+            if let Elemento::Simbolo(simbolo, _) = &elemento {
+                simbolos.push((simbolo.to_string(), tokens[i].coluna));
+            }
+            alternativas.last_mut().unwrap().push(elemento);
+        }
+        i += 1;
+    }
+    let coluna = tokens.get(fim).map(|t| t.coluna).unwrap_or(coluna_final);
+    verifica_vazio(alternativas.last().unwrap(), marcador, coluna, erros, linha);
+    alternativas
+}
+
+// a alternativa terminada na coluna dada é vazia só com o marcador, e o
+// marcador só vale sozinho; o %prec não conta como símbolo
+fn verifica_vazio(
+    alternativa: &[Elemento],
+    marcador: Option<usize>,
+    coluna: usize,
+    erros: &mut Vec<ErroDeLeitura>,
+    linha: usize,
+) {
+    let tem_simbolos = alternativa.iter().any(|e| !matches!(e, Elemento::Precedencia(_, _)));
+    match marcador {
+        Some(marcador) if tem_simbolos => erros.push(ErroDeLeitura {
+            linha,
+            coluna: marcador,
+            mensagem: "esperado ε sozinho na alternativa, encontrado junto de outros símbolos".to_string(),
+        }),
+        None if !tem_simbolos => erros.push(ErroDeLeitura {
+            linha,
+            coluna,
+            mensagem: "esperado um símbolo, ou ε para uma alternativa vazia".to_string(),
+        }),
+        _ => {}
+    }
+}
+
+fn coleta_simbolos(elementos: &[Elemento], simbolos: &mut Vec<String>) {
+    for elemento in elementos {
+        match elemento {
+            Elemento::Simbolo(simbolo, _) => simbolos.push(simbolo.to_string()),
+            Elemento::Grupo(alternativas, _) => {
+                for alternativa in alternativas {
+                    coleta_simbolos(alternativa, simbolos);
+                }
+            }
+            Elemento::Precedencia(_, _) => {}
+        }
+    }
+}
+
+// coluna de um %prec deixado dentro de algum grupo da alternativa
+fn precedencia_em_grupo(elementos: &[Elemento]) -> Option<usize> {
+    elementos.iter().find_map(|elemento| match elemento {
+        Elemento::Grupo(alternativas, _) => alternativas.iter().find_map(|alternativa| {
+            alternativa.iter().find_map(|e| match e {
+                Elemento::Precedencia(_, coluna) => Some(*coluna),
+                _ => None,
+            }).or_else(|| precedencia_em_grupo(alternativa))
+        }),
+        _ => None,
+    })
+}
+
+// expande os operadores EBNF em não terminais auxiliares nomeados A_1, A_2, ...
+// a partir do não terminal A da regra em que aparecem:
+//   X?  vira  A_n -> X | ε
+//   X*  vira  A_n -> ε | A_n X
+//   X+  vira  A_n -> X | A_n X
+// e um grupo com alternativas vira A_n -> α | β
+struct Expansor {
+    nomes_usados: Vec<String>,
+    auxiliares: Vec<RegraDeProducao>,
+    // linha da regra em expansão, herdada pelas regras auxiliares
+    linha: usize,
+}
+
+impl Expansor {
+    fn expande(&mut self, nao_terminal: &str, elementos: Vec<Elemento>) -> Vec<String> {
+        let mut producao: Vec<String> = Vec::new();
+        for elemento in elementos {
+            match elemento {
+                Elemento::Simbolo(simbolo, None) => producao.push(simbolo),
+                Elemento::Precedencia(_, _) => {}
+                Elemento::Simbolo(simbolo, Some(operador)) => {
+                    producao.push(self.aplica(nao_terminal, vec![vec![simbolo]], operador));
+                }
+                Elemento::Grupo(alternativas, operador) => {
+                    let mut expandidas: Vec<Vec<String>> = alternativas
+                        .into_iter()
+                        .map(|a| self.expande(nao_terminal, a))
+                        .collect();
+                    match operador {
+                        Some(operador) => producao.push(self.aplica(nao_terminal, expandidas, operador)),
+                        None if expandidas.len() == 1 => producao.append(&mut expandidas[0]),
+                        None => producao.push(self.novo_auxiliar(nao_terminal, expandidas)),
+                    }
+                }
+            }
+        }
+        producao
+    }
+
+    fn aplica(&mut self, nao_terminal: &str, alternativas: Vec<Vec<String>>, operador: char) -> String {
+        let sequencia = if alternativas.len() == 1 {
+            alternativas[0].to_vec()
+        } else {
+            vec![self.novo_auxiliar(nao_terminal, alternativas)]
+        };
+
+        let nome = self.novo_nome(nao_terminal);
+        let mut recursiva = vec![nome.to_string()];
+        recursiva.extend(sequencia.iter().cloned());
+        let producoes = match operador {
+            '?' => vec![sequencia, Vec::new()],
+            '*' => vec![Vec::new(), recursiva],
+            _ => vec![sequencia, recursiva],
+        };
+        for producao in producoes {
+            self.auxiliares.push(RegraDeProducao {
+                nao_terminal: nome.to_string(),
+                producao,
+                precedencia: None,
+                linha: self.linha,
+            });
+        }
+        nome
+    }
+
+    fn novo_auxiliar(&mut self, nao_terminal: &str, alternativas: Vec<Vec<String>>) -> String {
+        let nome = self.novo_nome(nao_terminal);
+        for producao in alternativas {
+            self.auxiliares.push(RegraDeProducao {
+                nao_terminal: nome.to_string(),
+                producao,
+                precedencia: None,
+                linha: self.linha,
+            });
+        }
+        nome
+    }
+
+    fn novo_nome(&mut self, nao_terminal: &str) -> String {
+        novo_nome(&mut self.nomes_usados, nao_terminal)
+    }
+}
+
+// novo não terminal A_1, A_2, ... que não colide com nenhum símbolo usado,
+// também usado pelas transformações da gramática
+pub(crate) fn novo_nome(usados: &mut Vec<String>, nao_terminal: &str) -> String {
+    let mut contador: usize = 1;
+    while usados.contains(&format!("{}_{}", nao_terminal, contador)) {
+        contador += 1;
+    }
+    let nome = format!("{}_{}", nao_terminal, contador);
+    usados.push(nome.to_string());
+    nome
+}
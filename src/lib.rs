@@ -0,0 +1,929 @@
+//! Geração de autômatos e tabelas LR (SLR, LALR, LR(1) canônico e Pager) e
+//! da tabela preditiva LL(1) a partir de uma gramática em texto.
+//!
+//! ```no_run
+//! let gramatica = slr1aux::le_gramatica("exemplo-slide.txt")?;
+//! let automato = slr1aux::gera_automato(gramatica, slr1aux::Algoritmo::Lalr)?;
+//! println!("{}", automato.tabela_md());
+//! // célula ACTION do estado I0 sob o primeiro terminal
+//! println!("{:?}", automato.acao(0, 0));
+//! # Ok::<(), slr1aux::Erro>(())
+//! ```
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use simple_matrix::Matrix;
+
+use leitor::obtem_regras_de_producao;
+
+pub use conjuntos::{Conjuntos, FIM_DA_ENTRADA};
+pub use contraexemplos::Contraexemplo;
+pub use identificadores::Identificadores;
+pub use leitor::{formata_aviso, formata_erro, Associatividade, ErroDeLeitura, INICIO_AUMENTADO};
+pub use ll1::{AnaliseLL1, ConflitoLL1};
+
+mod conjuntos;
+mod contraexemplos;
+mod identificadores;
+mod leitor;
+mod ll1;
+mod lr1;
+mod modulo_rust;
+mod padrao;
+mod precedencias;
+mod previsoes;
+mod transformacoes;
+mod vetores;
+mod verificacoes;
+#[cfg(test)]
+mod testes;
+
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct ItemLR {
+    producao: usize,
+    posicao_do_ponto: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct RegraDeProducao {
+    nao_terminal: String,
+    producao: Vec<String>,
+    // símbolo dado por %prec, que substitui a precedência do último terminal
+    precedencia: Option<String>,
+    // linha do arquivo em que a regra foi escrita; 0 na regra aumentada
+    linha: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Gramatica {
+    regras: Vec<RegraDeProducao>,
+    nao_terminais: Vec<String>,
+    terminais: Vec<String>,
+    // terminais com precedência declarada: nível e associatividade
+    precedencias: Vec<(String, usize, Associatividade)>,
+    // tokens declarados por %token, na ordem; None sem seção %token
+    tokens_declarados: Option<Vec<String>>,
+    // avisos que não impedem a geração do autômato
+    avisos: Vec<ErroDeLeitura>,
+}
+
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct Transicao {
+    simbolo: String,
+    itens: Vec<ItemLR>,
+    // estado destino, quando conhecido na construção; senão é obtido pelos itens
+    destino: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct Estado {
+    itens_iniciais: Vec<ItemLR>,
+    itens: Vec<ItemLR>,
+    transicoes: Vec<usize>,
+}
+
+impl ItemLR {
+    pub fn producao(&self) -> usize {
+        self.producao
+    }
+
+    pub fn posicao_do_ponto(&self) -> usize {
+        self.posicao_do_ponto
+    }
+}
+
+impl RegraDeProducao {
+    pub fn nao_terminal(&self) -> &str {
+        &self.nao_terminal
+    }
+
+    pub fn producao(&self) -> &[String] {
+        &self.producao
+    }
+
+    pub fn precedencia(&self) -> Option<&str> {
+        self.precedencia.as_deref()
+    }
+
+    pub fn linha(&self) -> usize {
+        self.linha
+    }
+}
+
+impl Gramatica {
+    pub fn regras(&self) -> &[RegraDeProducao] {
+        &self.regras
+    }
+
+    pub fn nao_terminais(&self) -> &[String] {
+        &self.nao_terminais
+    }
+
+    pub fn terminais(&self) -> &[String] {
+        &self.terminais
+    }
+
+    pub fn precedencias(&self) -> &[(String, usize, Associatividade)] {
+        &self.precedencias
+    }
+
+    pub fn tokens_declarados(&self) -> Option<&[String]> {
+        self.tokens_declarados.as_deref()
+    }
+
+    pub fn avisos(&self) -> &[ErroDeLeitura] {
+        &self.avisos
+    }
+}
+
+impl Transicao {
+    pub fn simbolo(&self) -> &str {
+        &self.simbolo
+    }
+
+    pub fn itens(&self) -> &[ItemLR] {
+        &self.itens
+    }
+
+    pub fn destino(&self) -> Option<usize> {
+        self.destino
+    }
+}
+
+impl Estado {
+    pub fn itens_iniciais(&self) -> &[ItemLR] {
+        &self.itens_iniciais
+    }
+
+    pub fn itens(&self) -> &[ItemLR] {
+        &self.itens
+    }
+
+    // índices em automato.transicoes() das transições que saem do estado
+    pub fn transicoes(&self) -> &[usize] {
+        &self.transicoes
+    }
+}
+
+// célula das tabelas ACTION (Empilha, Reduz, Aceita, Erro) e GOTO (VaiPara, Erro)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Acao {
+    Empilha(usize),
+    Reduz(usize),
+    Aceita,
+    #[default]
+    Erro,
+    VaiPara(usize),
+}
+
+impl fmt::Display for Acao {
+    // formato do relatório: I5, R2, ACEITAR, erro e, no GOTO, só o estado
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Acao::Empilha(estado) => write!(f, "I{}", estado),
+            Acao::Reduz(regra) => write!(f, "R{}", regra),
+            Acao::Aceita => write!(f, "ACEITAR"),
+            Acao::Erro => write!(f, "erro"),
+            Acao::VaiPara(estado) => write!(f, "{}", estado),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Conflito {
+    pub estado: usize,
+    pub simbolo: String,
+    // ações concorrentes e os itens que originam cada uma delas
+    pub acoes: Vec<(Acao, Vec<ItemLR>)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Algoritmo {
+    Slr,
+    Lalr,
+    Lr1,
+    Pager,
+}
+
+#[derive(Debug, Clone)]
+pub struct Automato {
+    algoritmo: Algoritmo,
+    gramatica: Gramatica,
+    estados: Vec<Estado>,
+    transicoes: Vec<Transicao>,
+    conjuntos: Conjuntos,
+    // símbolos de previsão de cada item, indexados como estados[i].itens[j]
+    previsoes: Vec<Vec<Vec<String>>>,
+    // ACTION: uma linha por estado e uma coluna por terminal, seguidos do $
+    tabela_de_acoes: Matrix<Acao>,
+    // GOTO: uma coluna por não terminal, na ordem de gramatica.nao_terminais;
+    // a do não terminal da regra aumentada fica sempre com Erro
+    tabela_de_desvios: Matrix<Acao>,
+    conflitos: Vec<Conflito>,
+    // conflitos empilha/reduz resolvidos pelas precedências, com a explicação
+    conflitos_resolvidos: Vec<(Conflito, String)>,
+}
+
+// erros das funções de alto nível, que leem o arquivo e geram o autômato
+#[derive(Debug)]
+pub enum Erro {
+    Arquivo(std::io::Error),
+    Gramatica(Vec<ErroDeLeitura>),
+    // conflitos que não foram resolvidos por precedência
+    Conflitos(Vec<Conflito>),
+    // gramática fora da forma esperada pela construção do autômato
+    GramaticaInvalida(Vec<String>),
+}
+
+impl fmt::Display for Erro {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Erro::Arquivo(erro) => write!(f, "não foi possível ler o arquivo da gramática: {}", erro),
+            Erro::Gramatica(erros) => {
+                for erro in erros.iter() {
+                    writeln!(f, "{}:{}: erro: {}", erro.linha, erro.coluna, erro.mensagem)?;
+                }
+                write!(f, "{} erro(s) na gramática", erros.len())
+            }
+            Erro::Conflitos(conflitos) => {
+                for conflito in conflitos.iter() {
+                    let acoes: Vec<String> = conflito.acoes.iter().map(|(acao, _)| acao.to_string()).collect();
+                    writeln!(
+                        f,
+                        "conflito no estado I{} sob o símbolo {}: {}",
+                        conflito.estado, conflito.simbolo, acoes.join("/"),
+                    )?;
+                }
+                write!(f, "{} conflito(s) não resolvido(s)", conflitos.len())
+            }
+            Erro::GramaticaInvalida(erros) => {
+                for erro in erros.iter() {
+                    writeln!(f, "erro: {}", erro)?;
+                }
+                write!(f, "{} erro(s) na estrutura da gramática", erros.len())
+            }
+        }
+    }
+}
+
+impl std::error::Error for Erro {}
+
+// arquivo da gramática lido, com o conteúdo guardado para formatar os erros e
+// os avisos com a linha em que aparecem
+#[derive(Debug, Clone)]
+pub struct ArquivoDeGramatica {
+    pub caminho: String,
+    pub conteudo: String,
+}
+
+impl ArquivoDeGramatica {
+    pub fn le(caminho: impl AsRef<Path>) -> Result<Self, Erro> {
+        Ok(ArquivoDeGramatica {
+            caminho: caminho.as_ref().display().to_string(),
+            conteudo: fs::read_to_string(caminho).map_err(Erro::Arquivo)?,
+        })
+    }
+
+    // gramática do arquivo, com todos os erros de leitura de uma vez; os
+    // avisos ficam em gramatica.avisos()
+    pub fn gramatica(&self) -> Result<Gramatica, Vec<ErroDeLeitura>> {
+        obtem_gramatica(self.linhas())
+    }
+
+    pub fn formata_erro(&self, erro: &ErroDeLeitura) -> String {
+        formata_erro(&self.caminho, &self.linhas(), erro)
+    }
+
+    pub fn formata_aviso(&self, aviso: &ErroDeLeitura) -> String {
+        formata_aviso(&self.caminho, &self.linhas(), aviso)
+    }
+
+    fn linhas(&self) -> Vec<&str> {
+        self.conteudo.split('\n').collect()
+    }
+}
+
+// lê e valida a gramática de um arquivo; os avisos ficam em gramatica.avisos()
+pub fn le_gramatica(caminho: impl AsRef<Path>) -> Result<Gramatica, Erro> {
+    ArquivoDeGramatica::le(caminho)?.gramatica().map_err(Erro::Gramatica)
+}
+
+// gera o autômato e a tabela, falhando se sobrar algum conflito. para obter a
+// tabela mesmo com conflitos, use Automato::constroi
+pub fn gera_automato(gramatica: Gramatica, algoritmo: Algoritmo) -> Result<Automato, Erro> {
+    let automato = Automato::constroi(gramatica, algoritmo)?;
+    if !automato.conflitos.is_empty() {
+        return Err(Erro::Conflitos(automato.conflitos));
+    }
+    Ok(automato)
+}
+
+pub fn obtem_gramatica(linhas_arquivo: Vec<&str>) -> Result<Gramatica, Vec<ErroDeLeitura>> {
+    let leitura = obtem_regras_de_producao(linhas_arquivo)?;
+    let regras = leitura.regras;
+    let nao_terminais = obtem_nao_terminais(regras.to_owned());
+    let mut gramatica = Gramatica {
+        regras: regras.to_owned(),
+        nao_terminais: nao_terminais.to_owned(),
+        terminais: obtem_terminais(regras, nao_terminais),
+        precedencias: leitura.precedencias,
+        tokens_declarados: leitura.tokens_declarados,
+        avisos: leitura.avisos,
+    };
+    let mut avisos = gramatica.verifica();
+    gramatica.avisos.append(&mut avisos);
+    Ok(gramatica)
+}
+
+fn obtem_nao_terminais(regras_de_producao: Vec<RegraDeProducao>) -> Vec<String> {
+    let mut nao_terminais: Vec<String> = Vec::new();
+    for regra_de_producao in regras_de_producao {
+        if !nao_terminais.contains(&regra_de_producao.nao_terminal) {
+            nao_terminais.push(regra_de_producao.nao_terminal);
+        }
+    }
+    nao_terminais
+}
+
+fn obtem_terminais(regras_de_producao: Vec<RegraDeProducao>, nao_terminais: Vec<String>) -> Vec<String> {
+    let mut terminais: Vec<String> = Vec::new();
+    for regra_de_producao in regras_de_producao {
+        for simbolo in regra_de_producao.producao {
+            if !terminais.contains(&simbolo) && !nao_terminais.contains(&simbolo) {
+                terminais.push(simbolo);
+            }
+        }
+    }
+    terminais
+}
+
+// compara dois conjuntos de itens sem considerar a ordem
+fn mesmos_itens(a: &[ItemLR], b: &[ItemLR]) -> bool {
+    a.len() == b.len() && a.iter().all(|i| b.contains(i))
+}
+
+impl Automato {
+    // constrói os estados do algoritmo escolhido e a tabela; os conflitos que
+    // sobrarem ficam em conflitos, com a ação mantida na tabela. a gramática é
+    // validada antes, para que a construção não falhe no meio
+    pub fn constroi(gramatica: Gramatica, algoritmo: Algoritmo) -> Result<Self, Erro> {
+        let erros = gramatica.valida();
+        if !erros.is_empty() {
+            return Err(Erro::GramaticaInvalida(erros));
+        }
+        let mut automato = Automato::inicializa(gramatica, algoritmo);
+        automato.analiza();
+        automato.gera_tabela();
+        Ok(automato)
+    }
+
+    pub fn algoritmo(&self) -> Algoritmo {
+        self.algoritmo
+    }
+
+    pub fn gramatica(&self) -> &Gramatica {
+        &self.gramatica
+    }
+
+    pub fn estados(&self) -> &[Estado] {
+        &self.estados
+    }
+
+    pub fn transicoes(&self) -> &[Transicao] {
+        &self.transicoes
+    }
+
+    pub fn conjuntos(&self) -> &Conjuntos {
+        &self.conjuntos
+    }
+
+    // símbolos de previsão de cada item, indexados como estados()[i].itens()[j]
+    pub fn previsoes(&self) -> &[Vec<Vec<String>>] {
+        &self.previsoes
+    }
+
+    // célula ACTION do estado sob o terminal, na ordem de gramatica().terminais(),
+    // ou sob o $ na coluna seguinte ao último terminal; None fora da tabela
+    pub fn acao(&self, estado: usize, coluna: usize) -> Option<Acao> {
+        self.tabela_de_acoes.get(estado, coluna).copied()
+    }
+
+    // célula GOTO do estado sob o não terminal, na ordem de
+    // gramatica().nao_terminais(); None fora da tabela
+    pub fn desvio(&self, estado: usize, nao_terminal: usize) -> Option<Acao> {
+        self.tabela_de_desvios.get(estado, nao_terminal).copied()
+    }
+
+    pub fn conflitos(&self) -> &[Conflito] {
+        &self.conflitos
+    }
+
+    pub fn conflitos_resolvidos(&self) -> &[(Conflito, String)] {
+        &self.conflitos_resolvidos
+    }
+
+    fn inicializa(gramatica: Gramatica, algoritmo: Algoritmo) -> Self {
+        Automato {
+            algoritmo,
+            conjuntos: gramatica.calcula_conjuntos(),
+            gramatica,
+            estados: Vec::new(),
+            transicoes: Vec::new(),
+            previsoes: Vec::new(),
+            tabela_de_acoes: Matrix::new(1,1),
+            tabela_de_desvios: Matrix::new(1,1),
+            conflitos: Vec::new(),
+            conflitos_resolvidos: Vec::new(),
+        }
+    }
+
+    fn analiza(&mut self) {
+        // as coleções LR(1) têm seus próprios estados e previsões
+        if self.algoritmo == Algoritmo::Lr1 || self.algoritmo == Algoritmo::Pager {
+            let estados = self.gera_estados_lr1(self.algoritmo == Algoritmo::Pager);
+            self.carrega_estados_lr1(estados);
+            return;
+        }
+
+        let item_inicial = ItemLR {
+            producao: 0,
+            posicao_do_ponto: 0,
+        };
+        self.gera_estado(vec![item_inicial]);
+
+        match self.algoritmo {
+            Algoritmo::Lalr => self.calcula_previsoes_lalr(),
+            _ => self.calcula_previsoes_slr(),
+        }
+    }
+
+    fn gera_estado(&mut self, itens_iniciais: Vec<ItemLR>) {
+        let mut itens: Vec<ItemLR> = Vec::new();
+        let mut transicoes: Vec<Transicao> = Vec::new();
+
+        // adiciona as produções iniciais ao vetor de itens
+        for item in itens_iniciais.iter() {
+            itens.push(item.clone());
+        }
+
+        // adiciona os desvios ao vetor de itens
+        let mut contador: usize = 0;
+        let mut tamanho_vetor_itens: usize = itens.len();
+        // se tamanho diferente, mais desvios devem ser analisados
+        while contador < tamanho_vetor_itens {
+            let item = itens[contador].clone();
+            // se não um item final
+            if item.posicao_do_ponto < self.gramatica.regras[item.producao].producao.len() {
+                let simbolo_marcado = self.gramatica.regras[item.producao].producao[item.posicao_do_ponto].to_string();
+                // se o símbolo marcado for um não terminal
+                if self.gramatica.nao_terminais.contains(&simbolo_marcado) {
+                    // percorre regras de produção
+                    for i in 0..self.gramatica.regras.len() {
+                        let producao = self.gramatica.regras[i].clone();
+                        // encontra produção com símbolo marcado pelo ponto
+                        if producao.nao_terminal == simbolo_marcado {
+                            let novo_item = ItemLR {
+                                producao: i,
+                                posicao_do_ponto: 0,
+                            };
+                            // se o item ainda não foi adicionado, adiciona
+                            if !itens.contains(&novo_item) {
+                                itens.push(novo_item);
+                            }
+                        }
+                    }
+                }
+                let transicao = Transicao {
+                    simbolo: simbolo_marcado,
+                    itens: vec![item],
+                    destino: None,
+                };
+                if !transicoes.contains(&transicao) {
+                    transicoes.push(transicao);
+                }
+            }
+            contador += 1;
+            tamanho_vetor_itens = itens.len();
+        }
+               
+        // junta transições sobre o mesmo símbolo
+        contador = 0;
+        let mut outro_contador: usize;
+        let mut tamanho_vetor_transicoes: usize = transicoes.len();
+        while contador < tamanho_vetor_transicoes {
+            outro_contador = contador + 1;
+            while outro_contador < tamanho_vetor_transicoes {
+                if transicoes[contador].simbolo == transicoes[outro_contador].simbolo {
+                    let item = transicoes[outro_contador].itens[0].clone();
+                    transicoes.remove(outro_contador);
+                    transicoes[contador].itens.push(item);
+                } else {
+                    // só avança se nada foi removido, senão o próximo seria pulado
+                    outro_contador += 1;
+                }
+                tamanho_vetor_transicoes = transicoes.len();
+            }
+            contador += 1;
+            tamanho_vetor_transicoes = transicoes.len();
+        }
+
+        // registra transições no vetor
+        let mut transicoes_index: Vec<usize> = Vec::new();
+        let transicoes_tamanho_anterior = self.transicoes.len();
+        for transicao in transicoes {
+            if let Some(index) = self.transicoes.iter().position(|t|
+                t.simbolo == transicao.simbolo && mesmos_itens(&t.itens, &transicao.itens)
+            ) {
+                transicoes_index.push(index);
+            } else {
+                transicoes_index.push(self.transicoes.len());
+                self.transicoes.push(transicao);
+            }
+        }
+
+        // registra estado
+        let estado = Estado {
+            itens_iniciais,
+            itens,
+            transicoes: transicoes_index.to_vec(),
+        };
+        self.estados.push(estado);
+
+        // gera estados sobre novas transições
+        for transicao in transicoes_index {
+            if transicao >= transicoes_tamanho_anterior {
+                let mut itens_da_trasicao: Vec<ItemLR> = Vec::new();
+                for item in self.transicoes[transicao].itens.clone() {
+                    itens_da_trasicao.push(item);
+                    let index = itens_da_trasicao.len();
+                    itens_da_trasicao[index - 1].posicao_do_ponto += 1; 
+                }
+                self.gera_estado(itens_da_trasicao);
+            }
+        }
+    }
+
+    fn obtem_estado(&self, transicao: Transicao) -> usize {
+        if let Some(destino) = transicao.destino {
+            return destino;
+        }
+
+        let mut destino: usize = 0;
+
+        for i in self.estados.iter() {
+            let mut itens = transicao.itens.to_vec();
+
+            for item in itens.iter_mut() {
+                item.posicao_do_ponto += 1;
+            }
+
+            if mesmos_itens(&i.itens_iniciais, &itens) {
+                break;
+            }
+
+            destino += 1;
+        }
+
+        destino
+    }
+
+    // estados com seus itens e transições, no formato do relatório
+    pub fn formata_estados(&self) -> String {
+        let mut string = String::new();
+        for i in 0..self.estados.len() {
+            string += format!("\n---------------------------------\nI{}:\n", i).as_ref();
+            for (j, item) in self.estados[i].itens.iter().enumerate() {
+                if self.algoritmo == Algoritmo::Slr {
+                    string += format!("  {}\n", self.formata_item(item)).as_ref();
+                } else {
+                    // itens no formato [A -> α.β, a/b], com os símbolos de previsão do estado
+                    string += format!("  {}, {}\n", self.formata_item(item), self.previsoes[i][j].join("/")).as_ref();
+                }
+            }
+            if !self.estados[i].transicoes.is_empty() {
+                string += "\n";
+                string += self.formata_transicoes(i).as_ref();
+            }
+        }
+        string
+    }
+
+    pub fn formata_item(&self, item: &ItemLR) -> String {
+        let mut string: String = self.gramatica.regras[item.producao].nao_terminal.to_string() + " ->";
+        // produção vazia: o item é sempre final
+        if self.gramatica.regras[item.producao].producao.is_empty() {
+            return string + " .";
+        }
+        for (i, simbolo) in self.gramatica.regras[item.producao].producao.iter().enumerate() {
+            if i == item.posicao_do_ponto {
+                string += " .";
+            } else {
+                string += " ";
+            }
+            string += simbolo;
+            if i + 1 == item.posicao_do_ponto && i + 2 == self.gramatica.regras[item.producao].producao.len() + 1 {
+                string += ".";
+            }
+        }
+        string
+    }
+
+    // relatório dos conflitos, primeiro os resolvidos por precedência e depois
+    // os que sobraram, com as ações, os itens e, se pedido, um contraexemplo de
+    // cada um
+    pub fn formata_conflitos(&self, com_contraexemplos: bool) -> String {
+        let mut string = String::new();
+        for (conflito, explicacao) in self.conflitos_resolvidos.iter() {
+            string += format!(
+                "conflito empilha/reduz no estado I{} sob o símbolo {} resolvido por precedência: {}\n",
+                conflito.estado, conflito.simbolo, explicacao,
+            ).as_ref();
+        }
+        if !self.conflitos_resolvidos.is_empty() {
+            string += format!("{} conflito(s) resolvido(s) por precedência\n", self.conflitos_resolvidos.len()).as_ref();
+        }
+        if self.conflitos.is_empty() {
+            return string;
+        }
+
+        let contraexemplos = if com_contraexemplos { self.contraexemplos() } else { Vec::new() };
+        for (i, conflito) in self.conflitos.iter().enumerate() {
+            let tipo = if conflito.acoes.iter().any(|(acao, _)| matches!(acao, Acao::Empilha(_))) {
+                "empilha/reduz"
+            } else {
+                "reduz/reduz"
+            };
+            string += format!("conflito {} no estado I{} sob o símbolo {}:\n", tipo, conflito.estado, conflito.simbolo).as_ref();
+            for (acao, itens) in conflito.acoes.iter() {
+                string += format!("  {}:\n", acao).as_ref();
+                for item in itens {
+                    string += format!("    {}\n", self.formata_item(item)).as_ref();
+                }
+            }
+            string += format!("  ação mantida na tabela: {}\n", conflito.acoes[0].0).as_ref();
+            if let Some(contraexemplo) = contraexemplos.get(i) {
+                string += contraexemplo.formata().as_ref();
+            }
+        }
+        string += format!("{} conflito(s) não resolvido(s)\n", self.conflitos.len()).as_ref();
+        string
+    }
+
+    fn formata_transicoes(&self, estado: usize) -> String {
+        let mut string = String::new();
+        for transicao in self.estados[estado].transicoes.iter() {
+            string += format!("δ(I{}, {}) = I{}\n",
+                estado,
+                self.transicoes[*transicao].simbolo,
+                self.obtem_estado(self.transicoes[*transicao].clone())
+            ).as_ref();
+        }
+        string
+    }
+
+    fn cabecalho_md(&self) -> String {
+        let mut string1: String = "| Estado ".to_string();
+        let mut string2: String = "|---".to_string();
+        for i in self.gramatica.terminais.iter() {
+            string1 = format!("{}| {} ", string1, i);
+            string2 = format!("{}|---", string2);
+        }
+        string1 = format!("{}| {} ", string1, FIM_DA_ENTRADA);
+        string2 = format!("{}|---", string2);
+        for i in self.gramatica.nao_terminais.iter() {
+            if i != INICIO_AUMENTADO {
+                string1 = format!("{}| {} ", string1, i);
+                string2 = format!("{}|---", string2);
+            }
+        }
+        format!("{}|\n{}|\n", string1, string2)
+    }
+
+    pub fn tabela_md(&self) -> String {
+        let mut string: String = self.cabecalho_md();
+
+        for i in 0..self.estados.len() {
+            string += format!("| I{} | ", i).as_ref();
+            for j in 0..=self.gramatica.terminais.len() {
+                string += format!("```{}``` | ", self.tabela_de_acoes.get(i, j).unwrap()).as_ref();
+            }
+            for j in 1..self.gramatica.nao_terminais.len() {
+                match self.tabela_de_desvios.get(i, j).unwrap() {
+                    Acao::Erro => string += "  | ",
+                    desvio => string += format!("```{}``` | ", desvio).as_ref(),
+                }
+            }
+            string += "\n";
+        }
+
+        string
+    }
+
+    // trecho para o liac, com os símbolos trocados pelos identificadores
+    pub fn tabela_rust(&self, identificadores: &Identificadores) -> String {
+        let mut string: String = String::new();
+
+        string += "let producoes = vec![\n";
+        for i in self.gramatica.regras.iter() {
+            let nao_terminal = self.gramatica.indice_nao_terminal(&i.nao_terminal).unwrap();
+            string += format!(
+                "    (NaoTerminais::{}, {} as usize),\n",
+                identificadores.nao_terminais[nao_terminal], i.producao.len(),
+            ).as_ref();
+        }
+        string += "];\n";
+
+        for i in 0..self.estados.len() {
+            string += format!("            {} => {{\n", i).as_ref();
+            // (padrão do símbolo, ação) de cada célula que não é erro
+            let mut condicoes: Vec<(String, String)> = Vec::new();
+            for j in 0..=self.gramatica.terminais.len() {
+                let simbolo = if j < self.gramatica.terminais.len() {
+                    format!("ElementosDaPilha::Tokens({})", identificadores.padrao_do_terminal(j))
+                } else {
+                    "ElementosDaPilha::Tokens(Tokens::Fim)".to_string()
+                };
+                let acao = match self.tabela_de_acoes.get(i, j).unwrap() {
+                    Acao::Empilha(estado) => format!("Acoes::Empilha({})", estado),
+                    Acao::Reduz(regra) => format!("Acoes::Reduz({})", regra),
+                    Acao::Aceita => "Acoes::Aceita".to_string(),
+                    _ => continue,
+                };
+                condicoes.push((simbolo, acao));
+            }
+            for j in 1..self.gramatica.nao_terminais.len() {
+                if let Acao::VaiPara(estado) = self.tabela_de_desvios.get(i, j).unwrap() {
+                    condicoes.push((
+                        format!("ElementosDaPilha::NaoTerminais(NaoTerminais::{})", identificadores.nao_terminais[j]),
+                        format!("Acoes::VaiPara({})", estado),
+                    ));
+                }
+            }
+            for (k, (simbolo, acao)) in condicoes.iter().enumerate() {
+                if k != 0 {
+                    string += "                } else if ";
+                } else {
+                    string += "                if ";
+                }
+                string += format!("let {} = simbolo {{\n                    return Ok({});\n", simbolo, acao).as_ref();
+            }
+            string += "                } else {\n                    return Ok(Acoes::Erro);\n                }\n            },\n";
+        }
+
+        string += "            _ => {\n                return Err(());\n            },\n";
+
+        string
+    }
+
+    fn gera_tabela(&mut self) {
+        // define as tabelas
+        let mut acoes: Matrix<Acao> = Matrix::new(self.estados.len(), self.gramatica.terminais.len() + 1);
+        let mut desvios: Matrix<Acao> = Matrix::new(self.estados.len(), self.gramatica.nao_terminais.len());
+        self.conflitos = Vec::new();
+        self.conflitos_resolvidos = Vec::new();
+
+        // estados
+        for i in 0..self.estados.len() {
+            let estado = self.estados[i].clone();
+
+            // colunas dos terminais
+            for j in 0..self.gramatica.terminais.len() {
+                let terminal = self.gramatica.terminais[j].clone();
+                let candidatas = self.determina_acao_terminal(i, &terminal);
+                acoes.set(i, j, self.escolhe_acao(i, terminal, candidatas));
+            }
+
+            // coluna do $
+            let candidatas = self.determina_acao_final(i);
+            acoes.set(
+                i,
+                self.gramatica.terminais.len(),
+                self.escolhe_acao(i, FIM_DA_ENTRADA.to_string(), candidatas)
+            );
+
+            // colunas dos não terminais, exceto o da regra aumentada
+            for j in 1..self.gramatica.nao_terminais.len() {
+                let nao_terminal = self.gramatica.nao_terminais[j].clone();
+                desvios.set(i, j, self.determina_acao_nao_terminal(estado.clone(), nao_terminal));
+            }
+        }
+        self.tabela_de_acoes = acoes;
+        self.tabela_de_desvios = desvios;
+    }
+
+    // escolhe a ação de uma célula da tabela, registrando um conflito caso haja
+    // mais de uma candidata. as ações chegam ordenadas por preferência: empilhar
+    // ou aceitar antes de reduzir, e reduções pela ordem das regras
+    fn escolhe_acao(&mut self, estado: usize, simbolo: String, acoes: Vec<(Acao, Vec<ItemLR>)>) -> Acao {
+        if acoes.is_empty() {
+            return Acao::Erro;
+        }
+
+        if let Some((acao, explicacao)) = self.resolve_por_precedencia(&simbolo, &acoes) {
+            self.conflitos_resolvidos.push((
+                Conflito {
+                    estado,
+                    simbolo,
+                    acoes,
+                },
+                explicacao,
+            ));
+            return acao;
+        }
+
+        let acao = acoes[0].0;
+        if acoes.len() > 1 {
+            self.conflitos.push(Conflito {
+                estado,
+                simbolo,
+                acoes,
+            });
+        }
+        acao
+    }
+
+    // adiciona as reduções possíveis sob o terminal, em ordem crescente de regra
+    fn adiciona_reducoes(&self, estado: usize, terminal: &str, acoes: &mut Vec<(Acao, Vec<ItemLR>)>) {
+        let mut reducoes: Vec<(usize, ItemLR)> = Vec::new();
+        for (i, item) in self.estados[estado].itens.iter().enumerate() {
+            // item LR em estado final que tem o terminal entre seus símbolos de previsão
+            if self.gramatica.regras[item.producao].producao.len() == item.posicao_do_ponto &&
+                self.previsoes[estado][i].iter().any(|t| t == terminal)
+            {
+                // o item já guarda o número de sua regra de produção; procurar a regra
+                // pelo lado direito confundiria regras como J -> Id ; e G -> Id ;
+                if item.producao > 0 {
+                    reducoes.push((item.producao, item.clone()));
+                }
+            }
+        }
+        reducoes.sort_by_key(|(regra, _)| *regra);
+
+        for (regra, item) in reducoes {
+            let acao = Acao::Reduz(regra);
+            if let Some(existente) = acoes.iter_mut().find(|(a, _)| *a == acao) {
+                existente.1.push(item);
+            } else {
+                acoes.push((acao, vec![item]));
+            }
+        }
+    }
+
+    fn determina_acao_terminal(&self, i: usize, terminal: &str) -> Vec<(Acao, Vec<ItemLR>)> {
+        let mut acoes: Vec<(Acao, Vec<ItemLR>)> = Vec::new();
+        let estado = &self.estados[i];
+
+        // se há transição sobre o terminal, empilha
+        if let Some(transicao) = estado.transicoes
+            .iter()
+            .find(|t| self.transicoes[**t].simbolo == terminal)
+        {
+            acoes.push((
+                Acao::Empilha(self.obtem_estado(self.transicoes[*transicao].clone())),
+                self.transicoes[*transicao].itens.to_vec(),
+            ));
+        }
+
+        // verifica também as reduções, que só serão usadas se não houver conflito
+        self.adiciona_reducoes(i, terminal, &mut acoes);
+
+        acoes
+    }
+
+    fn determina_acao_final(&self, i: usize) -> Vec<(Acao, Vec<ItemLR>)> {
+        let mut acoes: Vec<(Acao, Vec<ItemLR>)> = Vec::new();
+        let estado = &self.estados[i];
+
+        // verifica se há um item LR em estado final sobre a regra aumentada
+        // neste caso, aceita
+        if let Some(item) = estado.itens.iter().find(|i| 
+            (self.gramatica.regras[i.producao].nao_terminal == INICIO_AUMENTADO) &&
+            (self.gramatica.regras[i.producao].producao.len() == i.posicao_do_ponto)
+        ) {
+            acoes.push((Acao::Aceita, vec![item.clone()]));
+        }
+
+        // verifica também os itens LR em estado final sobre outros não terminais
+        // que não o da regra aumentada e que possam ser seguidos pelo fim da entrada
+        self.adiciona_reducoes(i, FIM_DA_ENTRADA, &mut acoes);
+
+        acoes
+    }
+
+    fn determina_acao_nao_terminal(&self, estado: Estado, nao_terminal: String) -> Acao {
+        let mut acao = Acao::Erro;
+
+        if nao_terminal != INICIO_AUMENTADO {
+            if let Some(transicao) = estado.transicoes
+                .iter()
+                .find(|t| self.transicoes[**t].simbolo == nao_terminal)
+            {
+                acao = Acao::VaiPara(self.obtem_estado(self.transicoes[*transicao].clone()));
+            }
+        }
+
+        acao
+    }
+}
@@ -0,0 +1,136 @@
+use crate::conjuntos::FIM_DA_ENTRADA;
+use crate::Gramatica;
+
+// tabela preditiva LL(1): M[A, a] lista as regras de A a usar quando o próximo
+// símbolo da entrada é a, marcando as que entraram pelo conjunto FOLLOW
+pub struct AnaliseLL1 {
+    pub gramatica: Gramatica,
+    // indexada como gramatica.nao_terminais e, nas colunas, como
+    // gramatica.terminais seguido do $
+    pub tabela: Vec<Vec<Vec<(usize, bool)>>>,
+    pub conflitos: Vec<ConflitoLL1>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConflitoLL1 {
+    pub nao_terminal: String,
+    pub simbolo: String,
+    // regras concorrentes e se cada uma entrou na célula pelo FOLLOW
+    pub regras: Vec<(usize, bool)>,
+}
+
+impl Gramatica {
+    // a regra A -> α vai para M[A, a] para cada a em FIRST(α) e, se α é
+    // anulável, para cada a em FOLLOW(A). a regra aumentada não entra na tabela
+    pub fn analisa_ll1(&self) -> AnaliseLL1 {
+        let conjuntos = self.calcula_conjuntos();
+        let mut colunas = self.terminais.to_vec();
+        colunas.push(FIM_DA_ENTRADA.to_string());
+        let mut tabela = vec![vec![Vec::new(); colunas.len()]; self.nao_terminais.len()];
+
+        for (r, regra) in self.regras.iter().enumerate().skip(1) {
+            let a = self.indice_nao_terminal(&regra.nao_terminal).unwrap();
+            let (primeiros, anulavel) = conjuntos.primeiros_da_sequencia(self, &regra.producao);
+            for terminal in primeiros.iter() {
+                let coluna = colunas.iter().position(|c| c == terminal).unwrap();
+                tabela[a][coluna].push((r, false));
+            }
+            if anulavel {
+                for terminal in conjuntos.seguintes[a].iter() {
+                    let coluna = colunas.iter().position(|c| c == terminal).unwrap();
+                    if !tabela[a][coluna].iter().any(|(outra, _)| *outra == r) {
+                        tabela[a][coluna].push((r, true));
+                    }
+                }
+            }
+        }
+
+        let mut conflitos: Vec<ConflitoLL1> = Vec::new();
+        for (a, linha) in tabela.iter().enumerate() {
+            for (coluna, regras) in linha.iter().enumerate() {
+                if regras.len() > 1 {
+                    conflitos.push(ConflitoLL1 {
+                        nao_terminal: self.nao_terminais[a].to_string(),
+                        simbolo: colunas[coluna].to_string(),
+                        regras: regras.to_vec(),
+                    });
+                }
+            }
+        }
+
+        AnaliseLL1 {
+            gramatica: self.clone(),
+            tabela,
+            conflitos,
+        }
+    }
+
+    pub fn formata_regra(&self, regra: usize) -> String {
+        let regra = &self.regras[regra];
+        if regra.producao.is_empty() {
+            format!("{} -> ε", regra.nao_terminal)
+        } else {
+            format!("{} -> {}", regra.nao_terminal, regra.producao.join(" "))
+        }
+    }
+}
+
+impl AnaliseLL1 {
+    pub fn formata_regras(&self) -> String {
+        let mut string = String::new();
+        for r in 1..self.gramatica.regras.len() {
+            string += format!("R{}: {}\n", r, self.gramatica.formata_regra(r)).as_ref();
+        }
+        string
+    }
+
+    // tabela no mesmo formato Markdown da tabela LR, com a regra mantida em
+    // cada célula; em caso de conflito, a de menor número
+    pub fn tabela_md(&self) -> String {
+        let mut string1: String = "| Não terminal ".to_string();
+        let mut string2: String = "|---".to_string();
+        for i in self.gramatica.terminais.iter() {
+            string1 = format!("{}| {} ", string1, i);
+            string2 = format!("{}|---", string2);
+        }
+        string1 = format!("{}| {} ", string1, FIM_DA_ENTRADA);
+        string2 = format!("{}|---", string2);
+        let mut string = format!("{}|\n{}|\n", string1, string2);
+
+        for (a, linha) in self.tabela.iter().enumerate().skip(1) {
+            string += format!("| {} | ", self.gramatica.nao_terminais[a]).as_ref();
+            for regras in linha.iter() {
+                match regras.first() {
+                    Some((regra, _)) => string += format!("```R{}``` | ", regra).as_ref(),
+                    None => string += "```erro``` | ",
+                }
+            }
+            string += "\n";
+        }
+
+        string
+    }
+
+    pub fn formata_conflitos(&self) -> String {
+        let mut string = String::new();
+        for conflito in self.conflitos.iter() {
+            let tipo = if conflito.regras.iter().filter(|(_, pelo_seguinte)| !pelo_seguinte).count() > 1 {
+                "FIRST/FIRST"
+            } else {
+                "FIRST/FOLLOW"
+            };
+            string += format!("conflito {} em M[{}, {}]:\n", tipo, conflito.nao_terminal, conflito.simbolo).as_ref();
+            for (regra, pelo_seguinte) in conflito.regras.iter() {
+                let origem = if *pelo_seguinte {
+                    format!(", {} está em FOLLOW({})", conflito.simbolo, conflito.nao_terminal)
+                } else {
+                    String::new()
+                };
+                string += format!("  R{}: {}{}\n", regra, self.gramatica.formata_regra(*regra), origem).as_ref();
+            }
+            string += format!("  regra mantida na tabela: R{}\n", conflito.regras[0].0).as_ref();
+        }
+        string += format!("{} conflito(s) LL(1)\n", self.conflitos.len()).as_ref();
+        string
+    }
+}
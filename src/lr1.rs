@@ -0,0 +1,335 @@
+use std::collections::VecDeque;
+
+use crate::conjuntos::FIM_DA_ENTRADA;
+use crate::{Automato, Estado, ItemLR, Transicao};
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct ItemLR1 {
+    pub producao: usize,
+    pub posicao_do_ponto: usize,
+    pub previsao: String,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct EstadoLR1 {
+    // itens iniciais ordenados, usados para identificar o estado
+    pub itens_iniciais: Vec<ItemLR1>,
+    pub itens: Vec<ItemLR1>,
+    // transições como pares (símbolo, estado destino)
+    pub desvios: Vec<(String, usize)>,
+}
+
+impl ItemLR1 {
+    pub(crate) fn nucleo(&self) -> ItemLR {
+        ItemLR {
+            producao: self.producao,
+            posicao_do_ponto: self.posicao_do_ponto,
+        }
+    }
+}
+
+impl Automato {
+    // fechamento LR(1): para cada [A -> α.Bβ, a] adiciona [B -> .γ, b] para
+    // todo b em FIRST(βa)
+    pub(crate) fn fechamento_lr1(&self, itens_iniciais: &[ItemLR1]) -> Vec<ItemLR1> {
+        let mut itens: Vec<ItemLR1> = itens_iniciais.to_vec();
+
+        let mut contador: usize = 0;
+        while contador < itens.len() {
+            let item = itens[contador].clone();
+            let producao = &self.gramatica.regras[item.producao].producao;
+            if item.posicao_do_ponto < producao.len() {
+                let simbolo_marcado = &producao[item.posicao_do_ponto];
+                if self.gramatica.nao_terminais.contains(simbolo_marcado) {
+                    let (mut previsoes, anulavel) = self.conjuntos.primeiros_da_sequencia(
+                        &self.gramatica,
+                        &producao[item.posicao_do_ponto + 1..],
+                    );
+                    if anulavel {
+                        previsoes.push(item.previsao.to_string());
+                    }
+                    for (i, regra) in self.gramatica.regras.iter().enumerate() {
+                        if regra.nao_terminal == *simbolo_marcado {
+                            for previsao in previsoes.iter() {
+                                let novo_item = ItemLR1 {
+                                    producao: i,
+                                    posicao_do_ponto: 0,
+                                    previsao: previsao.to_string(),
+                                };
+                                if !itens.contains(&novo_item) {
+                                    itens.push(novo_item);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            contador += 1;
+        }
+
+        itens
+    }
+
+    // desvio LR(1): avança o ponto sobre o símbolo, retornando os itens
+    // iniciais do estado destino já ordenados
+    pub(crate) fn desvio_lr1(&self, itens: &[ItemLR1], simbolo: &str) -> Vec<ItemLR1> {
+        let mut avancados: Vec<ItemLR1> = Vec::new();
+        for item in itens {
+            let producao = &self.gramatica.regras[item.producao].producao;
+            if item.posicao_do_ponto < producao.len() && producao[item.posicao_do_ponto] == simbolo {
+                let mut avancado = item.clone();
+                avancado.posicao_do_ponto += 1;
+                if !avancados.contains(&avancado) {
+                    avancados.push(avancado);
+                }
+            }
+        }
+        avancados.sort();
+        avancados
+    }
+
+    // símbolos marcados pelo ponto, na ordem em que aparecem nos itens
+    pub(crate) fn simbolos_marcados(&self, itens: &[ItemLR1]) -> Vec<String> {
+        let mut simbolos: Vec<String> = Vec::new();
+        for item in itens {
+            let producao = &self.gramatica.regras[item.producao].producao;
+            if item.posicao_do_ponto < producao.len() && !simbolos.contains(&producao[item.posicao_do_ponto]) {
+                simbolos.push(producao[item.posicao_do_ponto].to_string());
+            }
+        }
+        simbolos
+    }
+
+    // coleção LR(1) partindo de [$inicio -> .S, $]. sem mesclagem é a coleção
+    // canônica; com mesclagem, um novo estado é unido a um existente de mesmo
+    // núcleo sempre que forem fracamente compatíveis (Pager), o que mantém o
+    // poder do LR(1) com um número de estados próximo ao do LALR(1)
+    pub(crate) fn gera_estados_lr1(&self, mesclagem: bool) -> Vec<EstadoLR1> {
+        let item_inicial = ItemLR1 {
+            producao: 0,
+            posicao_do_ponto: 0,
+            previsao: FIM_DA_ENTRADA.to_string(),
+        };
+        let mut estados: Vec<EstadoLR1> = vec![EstadoLR1 {
+            itens_iniciais: vec![item_inicial.clone()],
+            itens: self.fechamento_lr1(&[item_inicial]),
+            desvios: Vec::new(),
+        }];
+
+        // estados a processar; um estado mesclado que ganhou previsões volta à fila
+        let mut pendentes: VecDeque<usize> = VecDeque::from(vec![0]);
+        while let Some(atual) = pendentes.pop_front() {
+            let itens = estados[atual].itens.clone();
+            let desvios_anteriores = std::mem::take(&mut estados[atual].desvios);
+            for simbolo in self.simbolos_marcados(&itens) {
+                let itens_iniciais = self.desvio_lr1(&itens, &simbolo);
+                let mut destino = estados.iter().position(|e| e.itens_iniciais == itens_iniciais);
+
+                if destino.is_none() && mesclagem {
+                    // tenta primeiro o destino anterior, depois os demais estados de mesmo núcleo
+                    let mut candidatos: Vec<usize> = desvios_anteriores
+                        .iter()
+                        .filter(|(s, _)| *s == simbolo)
+                        .map(|(_, d)| *d)
+                        .collect();
+                    candidatos.extend(0..estados.len());
+                    destino = candidatos
+                        .into_iter()
+                        .find(|c| fracamente_compativeis(&estados[*c].itens_iniciais, &itens_iniciais));
+
+                    if let Some(c) = destino {
+                        let tamanho_anterior = estados[c].itens_iniciais.len();
+                        for item in itens_iniciais.iter() {
+                            if !estados[c].itens_iniciais.contains(item) {
+                                estados[c].itens_iniciais.push(item.clone());
+                            }
+                        }
+                        if estados[c].itens_iniciais.len() > tamanho_anterior {
+                            estados[c].itens_iniciais.sort();
+                            estados[c].itens = self.fechamento_lr1(&estados[c].itens_iniciais);
+                            if !pendentes.contains(&c) {
+                                pendentes.push_back(c);
+                            }
+                        }
+                    }
+                }
+
+                let destino = match destino {
+                    Some(destino) => destino,
+                    None => {
+                        estados.push(EstadoLR1 {
+                            itens: self.fechamento_lr1(&itens_iniciais),
+                            itens_iniciais,
+                            desvios: Vec::new(),
+                        });
+                        pendentes.push_back(estados.len() - 1);
+                        estados.len() - 1
+                    }
+                };
+                estados[atual].desvios.push((simbolo, destino));
+            }
+        }
+
+        if mesclagem {
+            remove_inalcancaveis(estados)
+        } else {
+            estados
+        }
+    }
+
+    // converte os estados LR(1) para a representação do autômato: os itens de
+    // cada estado são os núcleos LR(0) e as previsões de um mesmo núcleo são unidas
+    pub(crate) fn carrega_estados_lr1(&mut self, estados: Vec<EstadoLR1>) {
+        self.estados = Vec::new();
+        self.transicoes = Vec::new();
+        self.previsoes = Vec::new();
+
+        for estado in estados.iter() {
+            let mut itens_iniciais: Vec<ItemLR> = Vec::new();
+            for item in estado.itens_iniciais.iter() {
+                if !itens_iniciais.contains(&item.nucleo()) {
+                    itens_iniciais.push(item.nucleo());
+                }
+            }
+
+            let mut itens: Vec<ItemLR> = Vec::new();
+            let mut previsoes: Vec<Vec<String>> = Vec::new();
+            for item in estado.itens.iter() {
+                match itens.iter().position(|i| *i == item.nucleo()) {
+                    Some(i) => {
+                        if !previsoes[i].contains(&item.previsao) {
+                            previsoes[i].push(item.previsao.to_string());
+                        }
+                    }
+                    None => {
+                        itens.push(item.nucleo());
+                        previsoes.push(vec![item.previsao.to_string()]);
+                    }
+                }
+            }
+
+            let mut transicoes: Vec<usize> = Vec::new();
+            for (simbolo, destino) in estado.desvios.iter() {
+                let mut itens_da_transicao: Vec<ItemLR> = Vec::new();
+                for item in itens.iter() {
+                    let producao = &self.gramatica.regras[item.producao].producao;
+                    if item.posicao_do_ponto < producao.len() && producao[item.posicao_do_ponto] == *simbolo {
+                        itens_da_transicao.push(item.clone());
+                    }
+                }
+                transicoes.push(self.transicoes.len());
+                self.transicoes.push(Transicao {
+                    simbolo: simbolo.to_string(),
+                    itens: itens_da_transicao,
+                    destino: Some(*destino),
+                });
+            }
+
+            self.estados.push(Estado {
+                itens_iniciais,
+                itens,
+                transicoes,
+            });
+            self.previsoes.push(previsoes);
+        }
+    }
+}
+
+impl Automato {
+    // compara os estados obtidos com a mesclagem com os do LALR(1), que tem
+    // exatamente um estado por núcleo LR(0)
+    pub fn resumo_da_mesclagem(&self) -> String {
+        let mut nucleos: Vec<(Vec<ItemLR>, Vec<usize>)> = Vec::new();
+        for (i, estado) in self.estados.iter().enumerate() {
+            let mut nucleo = estado.itens_iniciais.to_vec();
+            nucleo.sort_by_key(|item| (item.producao, item.posicao_do_ponto));
+            match nucleos.iter_mut().find(|(n, _)| *n == nucleo) {
+                Some((_, estados)) => estados.push(i),
+                None => nucleos.push((nucleo, vec![i])),
+            }
+        }
+
+        let divididos: Vec<&(Vec<ItemLR>, Vec<usize>)> = nucleos.iter().filter(|(_, e)| e.len() > 1).collect();
+        let mut string = format!(
+            "{} estados, {} no LALR(1): {} núcleo(s) dividido(s), {} estado(s) a mais\n",
+            self.estados.len(),
+            nucleos.len(),
+            divididos.len(),
+            self.estados.len() - nucleos.len(),
+        );
+        for (_, estados) in divididos {
+            let nomes: Vec<String> = estados.iter().map(|e| format!("I{}", e)).collect();
+            string += format!("  núcleo dividido em {}\n", nomes.join(", ")).as_ref();
+        }
+        string
+    }
+}
+
+// agrupa itens ordenados pelo núcleo LR(0), unindo suas previsões
+fn agrupa_por_nucleo(itens: &[ItemLR1]) -> Vec<(ItemLR, Vec<&str>)> {
+    let mut grupos: Vec<(ItemLR, Vec<&str>)> = Vec::new();
+    for item in itens {
+        match grupos.last_mut() {
+            Some((nucleo, previsoes)) if *nucleo == item.nucleo() => previsoes.push(&item.previsao),
+            _ => grupos.push((item.nucleo(), vec![&item.previsao])),
+        }
+    }
+    grupos
+}
+
+// compatibilidade fraca de Pager: dois conjuntos de itens iniciais com o mesmo
+// núcleo podem ser unidos se, para todo par de itens i != j, a união não cria
+// uma previsão comum a i e j que já não existisse em um dos dois estados
+fn fracamente_compativeis(a: &[ItemLR1], b: &[ItemLR1]) -> bool {
+    let grupos_a = agrupa_por_nucleo(a);
+    let grupos_b = agrupa_por_nucleo(b);
+    if grupos_a.len() != grupos_b.len() || grupos_a.iter().zip(grupos_b.iter()).any(|(x, y)| x.0 != y.0) {
+        return false;
+    }
+
+    let intersectam = |x: &Vec<&str>, y: &Vec<&str>| x.iter().any(|s| y.contains(s));
+    for i in 0..grupos_a.len() {
+        for j in (i + 1)..grupos_a.len() {
+            let cruzadas = intersectam(&grupos_a[i].1, &grupos_b[j].1) || intersectam(&grupos_b[i].1, &grupos_a[j].1);
+            let ja_existentes = intersectam(&grupos_a[i].1, &grupos_a[j].1) || intersectam(&grupos_b[i].1, &grupos_b[j].1);
+            if cruzadas && !ja_existentes {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+// a mesclagem pode redirecionar transições e deixar estados órfãos; estes são
+// removidos e os demais renumerados mantendo a ordem
+fn remove_inalcancaveis(estados: Vec<EstadoLR1>) -> Vec<EstadoLR1> {
+    let mut alcancaveis: Vec<bool> = vec![false; estados.len()];
+    let mut pilha: Vec<usize> = vec![0];
+    while let Some(estado) = pilha.pop() {
+        if !alcancaveis[estado] {
+            alcancaveis[estado] = true;
+            pilha.extend(estados[estado].desvios.iter().map(|(_, d)| *d));
+        }
+    }
+
+    let mut novos_indices: Vec<usize> = Vec::new();
+    let mut contador: usize = 0;
+    for alcancavel in alcancaveis.iter() {
+        novos_indices.push(contador);
+        if *alcancavel {
+            contador += 1;
+        }
+    }
+
+    estados
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| alcancaveis[*i])
+        .map(|(_, mut estado)| {
+            for desvio in estado.desvios.iter_mut() {
+                desvio.1 = novos_indices[desvio.1];
+            }
+            estado
+        })
+        .collect()
+}
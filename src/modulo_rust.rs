@@ -0,0 +1,131 @@
+use crate::{Acao, Algoritmo, Automato, Identificadores};
+
+// analisador empilha/reduz genérico, igual para toda gramática
+const ANALISADOR: &str = "\
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErroDeSintaxe {
+    // posição do token na entrada, a partir de 0; o fim da entrada tem a
+    // posição igual ao número de tokens
+    pub posicao: usize,
+    pub token: Tokens,
+    pub estado: usize,
+}
+
+// analisa os tokens, sem o Fim, que é acrescentado aqui, e devolve as regras
+// usadas nas reduções na ordem em que foram aplicadas, isto é, a derivação
+// mais à direita ao contrário
+pub fn analisa<I: IntoIterator<Item = Tokens>>(tokens: I) -> Result<Vec<usize>, ErroDeSintaxe> {
+    let mut tokens = tokens.into_iter();
+    let mut pilha: Vec<usize> = vec![0];
+    let mut reducoes: Vec<usize> = Vec::new();
+    let mut posicao: usize = 0;
+    let mut token = tokens.next().unwrap_or(Tokens::Fim);
+    loop {
+        let estado = *pilha.last().unwrap();
+        match acao(estado, token) {
+            Acoes::Empilha(destino) => {
+                pilha.push(destino);
+                posicao += 1;
+                token = tokens.next().unwrap_or(Tokens::Fim);
+            }
+            Acoes::Reduz(regra) => {
+                let (nao_terminal, tamanho) = PRODUCOES[regra - 1];
+                pilha.truncate(pilha.len() - tamanho);
+                let topo = *pilha.last().unwrap();
+                pilha.push(desvio(topo, nao_terminal).expect(\"desvio ausente na tabela GOTO\"));
+                reducoes.push(regra);
+            }
+            Acoes::Aceita => return Ok(reducoes),
+            Acoes::Erro => return Err(ErroDeSintaxe { posicao, token, estado }),
+        }
+    }
+}
+";
+
+impl Automato {
+    // módulo Rust completo, que compila sozinho: enums dos tokens e dos não
+    // terminais, as produções, as tabelas ACTION e GOTO como funções e o
+    // analisador, com os símbolos trocados pelos identificadores
+    pub fn modulo_rust(&self, identificadores: &Identificadores) -> String {
+        let nome_do_algoritmo = match self.algoritmo {
+            Algoritmo::Slr => "SLR(1)",
+            Algoritmo::Lalr => "LALR(1)",
+            Algoritmo::Lr1 => "LR(1) canônico",
+            Algoritmo::Pager => "LR(1) de Pager",
+        };
+        let mut string = format!("// analisador {} gerado pelo slr1aux\n\n", nome_do_algoritmo);
+
+        // nomes como E_1, dos não terminais auxiliares, não são CamelCase
+        string += "#[allow(non_camel_case_types)]\n#[derive(Debug, Clone, Copy, PartialEq, Eq)]\npub enum Tokens {\n";
+        for (i, terminal) in self.gramatica.terminais.iter().enumerate() {
+            string += variante(&identificadores.terminais[i], terminal).as_ref();
+        }
+        string += "    // fim da entrada\n    Fim,\n}\n\n";
+
+        string += "#[allow(non_camel_case_types)]\n#[derive(Debug, Clone, Copy, PartialEq, Eq)]\npub enum NaoTerminais {\n";
+        for (i, nao_terminal) in self.gramatica.nao_terminais.iter().enumerate().skip(1) {
+            string += variante(&identificadores.nao_terminais[i], nao_terminal).as_ref();
+        }
+        string += "}\n\n";
+
+        string += "#[derive(Debug, Clone, Copy, PartialEq, Eq)]\npub enum Acoes {\n    \
+                   Empilha(usize),\n    Reduz(usize),\n    Aceita,\n    Erro,\n}\n\n";
+
+        // a regra 0 é a aumentada, que nunca é reduzida
+        string += "// não terminal e número de símbolos do lado direito de cada regra, a\n\
+                   // partir da regra 1: a regra R está em PRODUCOES[R - 1]\n";
+        string += format!(
+            "pub const PRODUCOES: [(NaoTerminais, usize); {}] = [\n",
+            self.gramatica.regras.len() - 1,
+        ).as_ref();
+        for (r, regra) in self.gramatica.regras.iter().enumerate().skip(1) {
+            let nao_terminal = self.gramatica.indice_nao_terminal(&regra.nao_terminal).unwrap();
+            string += format!(
+                "    (NaoTerminais::{}, {}), // R{}: {}\n",
+                identificadores.nao_terminais[nao_terminal], regra.producao.len(), r, self.gramatica.formata_regra(r),
+            ).as_ref();
+        }
+        string += "];\n\n";
+
+        string += "pub fn acao(estado: usize, token: Tokens) -> Acoes {\n    match (estado, token) {\n";
+        for i in 0..self.estados.len() {
+            for j in 0..=self.gramatica.terminais.len() {
+                let token = identificadores.terminais.get(j).map(|t| t.as_str()).unwrap_or("Fim");
+                let acao = match self.tabela_de_acoes.get(i, j).unwrap() {
+                    Acao::Empilha(estado) => format!("Acoes::Empilha({})", estado),
+                    Acao::Reduz(regra) => format!("Acoes::Reduz({})", regra),
+                    Acao::Aceita => "Acoes::Aceita".to_string(),
+                    _ => continue,
+                };
+                string += format!("        ({}, Tokens::{}) => {},\n", i, token, acao).as_ref();
+            }
+        }
+        string += "        _ => Acoes::Erro,\n    }\n}\n\n";
+
+        string += "pub fn desvio(estado: usize, nao_terminal: NaoTerminais) -> Option<usize> {\n    \
+                   match (estado, nao_terminal) {\n";
+        for i in 0..self.estados.len() {
+            for j in 1..self.gramatica.nao_terminais.len() {
+                if let Acao::VaiPara(destino) = self.tabela_de_desvios.get(i, j).unwrap() {
+                    string += format!(
+                        "        ({}, NaoTerminais::{}) => Some({}),\n",
+                        i, identificadores.nao_terminais[j], destino,
+                    ).as_ref();
+                }
+            }
+        }
+        string += "        _ => None,\n    }\n}\n\n";
+
+        string += ANALISADOR;
+        string
+    }
+}
+
+// variante de um enum, com o símbolo da gramática ao lado quando o nome muda
+fn variante(identificador: &str, simbolo: &str) -> String {
+    if identificador == simbolo {
+        format!("    {},\n", identificador)
+    } else {
+        format!("    {}, // {}\n", identificador, simbolo)
+    }
+}
@@ -0,0 +1,126 @@
+// padrão simples, no estilo de uma expressão regular, para a convenção de
+// nomes dos não terminais. aceita caracteres literais, . (qualquer caractere),
+// classes como [A-Z] ou [^a-z], \ para escapar e os quantificadores ?, * e +.
+// o padrão precisa casar com o símbolo inteiro; ^ no início e $ no fim são
+// aceitos e não mudam nada. alternativas, grupos e repetições contadas, com |,
+// ( ), { }, são recusados em vez de lidos como caracteres
+#[derive(Debug, Clone)]
+pub struct Padrao {
+    elementos: Vec<(Classe, Quantificador)>,
+}
+
+#[derive(Debug, Clone)]
+enum Classe {
+    Qualquer,
+    Caractere(char),
+    // intervalos de caracteres e se a classe é negada
+    Conjunto(Vec<(char, char)>, bool),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Quantificador {
+    Um,
+    Opcional,
+    ZeroOuMais,
+    UmOuMais,
+}
+
+impl Padrao {
+    pub fn novo(texto: &str) -> Result<Padrao, String> {
+        let texto = texto.strip_prefix('^').unwrap_or(texto);
+        let texto = match texto.strip_suffix('$') {
+            Some(resto) if !resto.ends_with('\\') => resto,
+            _ => texto,
+        };
+
+        let mut elementos: Vec<(Classe, Quantificador)> = Vec::new();
+        let mut caracteres = texto.chars().peekable();
+        while let Some(c) = caracteres.next() {
+            let classe = match c {
+                '.' => Classe::Qualquer,
+                '\\' => match caracteres.next() {
+                    Some(escapado) => Classe::Caractere(escapado),
+                    None => return Err("esperado um caractere após \\".to_string()),
+                },
+                '[' => {
+                    let negado = caracteres.next_if_eq(&'^').is_some();
+                    let mut intervalos: Vec<(char, char)> = Vec::new();
+                    loop {
+                        let inicio = match caracteres.next() {
+                            Some(']') if !intervalos.is_empty() => break,
+                            Some('\\') => caracteres.next().ok_or("esperado um caractere após \\")?,
+                            Some(inicio) => inicio,
+                            None => return Err("esperado ] no fim da classe".to_string()),
+                        };
+                        let mut fim = inicio;
+                        if caracteres.peek() == Some(&'-') {
+                            caracteres.next();
+                            match caracteres.next() {
+                                Some(']') => {
+                                    // - no fim da classe é literal
+                                    intervalos.push((inicio, inicio));
+                                    intervalos.push(('-', '-'));
+                                    break;
+                                }
+                                Some(f) => fim = f,
+                                None => return Err("esperado ] no fim da classe".to_string()),
+                            }
+                        }
+                        if fim < inicio {
+                            return Err(format!("intervalo {}-{} invertido", inicio, fim));
+                        }
+                        intervalos.push((inicio, fim));
+                    }
+                    Classe::Conjunto(intervalos, negado)
+                }
+                '?' | '*' | '+' => return Err(format!("esperado um caractere ou classe antes de {}", c)),
+                '|' | '(' | ')' | '{' | '}' => {
+                    return Err(format!("{} não é suportado; use \\{} para o caractere", c, c));
+                }
+                _ => Classe::Caractere(c),
+            };
+            let quantificador = match caracteres.peek() {
+                Some('?') => Quantificador::Opcional,
+                Some('*') => Quantificador::ZeroOuMais,
+                Some('+') => Quantificador::UmOuMais,
+                _ => Quantificador::Um,
+            };
+            if quantificador != Quantificador::Um {
+                caracteres.next();
+            }
+            elementos.push((classe, quantificador));
+        }
+        Ok(Padrao { elementos })
+    }
+
+    pub fn casa(&self, simbolo: &str) -> bool {
+        let caracteres: Vec<char> = simbolo.chars().collect();
+        casa_a_partir(&self.elementos, &caracteres)
+    }
+}
+
+impl Classe {
+    fn aceita(&self, c: char) -> bool {
+        match self {
+            Classe::Qualquer => true,
+            Classe::Caractere(esperado) => c == *esperado,
+            Classe::Conjunto(intervalos, negado) => intervalos.iter().any(|(i, f)| *i <= c && c <= *f) != *negado,
+        }
+    }
+}
+
+// casa os elementos com todos os caracteres, tentando primeiro as repetições
+// mais longas e voltando atrás quando o restante não casa
+fn casa_a_partir(elementos: &[(Classe, Quantificador)], caracteres: &[char]) -> bool {
+    let Some(((classe, quantificador), restantes)) = elementos.split_first() else {
+        return caracteres.is_empty();
+    };
+    let (minimo, maximo) = match quantificador {
+        Quantificador::Um => (1, 1),
+        Quantificador::Opcional => (0, 1),
+        Quantificador::ZeroOuMais => (0, caracteres.len()),
+        Quantificador::UmOuMais => (1, caracteres.len()),
+    };
+    let aceitos = caracteres.iter().take(maximo).take_while(|c| classe.aceita(**c)).count();
+    (minimo..=aceitos).rev().any(|n| casa_a_partir(restantes, &caracteres[n..]))
+}
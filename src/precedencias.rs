@@ -0,0 +1,52 @@
+use crate::leitor::Associatividade;
+use crate::{Acao, Automato, Gramatica, ItemLR};
+
+impl Gramatica {
+    pub fn precedencia_do_terminal(&self, terminal: &str) -> Option<(usize, Associatividade)> {
+        self.precedencias
+            .iter()
+            .find(|(simbolo, _, _)| simbolo == terminal)
+            .map(|(_, nivel, associatividade)| (*nivel, *associatividade))
+    }
+
+    // nível de precedência da regra: o do símbolo dado por %prec ou, na falta
+    // dele, o do último terminal do lado direito que tenha precedência
+    pub fn precedencia_da_regra(&self, regra: usize) -> Option<usize> {
+        let regra = &self.regras[regra];
+        match &regra.precedencia {
+            Some(simbolo) => self.precedencia_do_terminal(simbolo).map(|(nivel, _)| nivel),
+            None => regra.producao
+                .iter()
+                .rev()
+                .find_map(|simbolo| self.precedencia_do_terminal(simbolo))
+                .map(|(nivel, _)| nivel),
+        }
+    }
+}
+
+impl Automato {
+    // resolve um conflito entre empilhar e uma única redução como o yacc: vence
+    // a de maior precedência; com a mesma, %left reduz, %right empilha e
+    // %nonassoc torna a célula um erro. devolve a ação escolhida e a explicação,
+    // ou None se o conflito não puder ser resolvido assim
+    pub(crate) fn resolve_por_precedencia(&self, simbolo: &str, acoes: &[(Acao, Vec<ItemLR>)]) -> Option<(Acao, String)> {
+        let [(empilha @ Acao::Empilha(_), _), (reduz @ Acao::Reduz(_), itens)] = acoes else {
+            return None;
+        };
+        let (nivel_do_terminal, associatividade) = self.gramatica.precedencia_do_terminal(simbolo)?;
+        let nivel_da_regra = self.gramatica.precedencia_da_regra(itens[0].producao)?;
+
+        let resolucao = if nivel_da_regra > nivel_do_terminal {
+            (*reduz, format!("a regra {} tem precedência maior que {}", reduz, simbolo))
+        } else if nivel_da_regra < nivel_do_terminal {
+            (*empilha, format!("{} tem precedência maior que a regra {}", simbolo, reduz))
+        } else {
+            match associatividade {
+                Associatividade::Esquerda => (*reduz, format!("{} é associativo à esquerda", simbolo)),
+                Associatividade::Direita => (*empilha, format!("{} é associativo à direita", simbolo)),
+                Associatividade::NaoAssociativa => (Acao::Erro, format!("{} não é associativo", simbolo)),
+            }
+        };
+        Some((resolucao.0, format!("{} ({})", resolucao.0, resolucao.1)))
+    }
+}
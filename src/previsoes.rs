@@ -0,0 +1,102 @@
+use crate::conjuntos::{adiciona, FIM_DA_ENTRADA};
+use crate::{Automato, ItemLR};
+
+impl Automato {
+    // SLR(1): os itens finais podem ser reduzidos sob qualquer terminal do
+    // conjunto FOLLOW do não terminal da regra
+    pub(crate) fn calcula_previsoes_slr(&mut self) {
+        let mut previsoes: Vec<Vec<Vec<String>>> = Vec::new();
+        for estado in self.estados.iter() {
+            let mut previsoes_do_estado: Vec<Vec<String>> = Vec::new();
+            for item in estado.itens.iter() {
+                let nao_terminal = &self.gramatica.regras[item.producao].nao_terminal;
+                match self.gramatica.indice_nao_terminal(nao_terminal) {
+                    Some(a) => previsoes_do_estado.push(self.conjuntos.seguintes[a].to_vec()),
+                    None => previsoes_do_estado.push(Vec::new()),
+                }
+            }
+            previsoes.push(previsoes_do_estado);
+        }
+        self.previsoes = previsoes;
+    }
+
+    // LALR(1): propaga os símbolos de previsão sobre a coleção LR(0) até que
+    // nenhum conjunto mude. dentro de um estado, o fechamento de [A -> α.Bβ, a]
+    // gera FIRST(βa) para os itens B -> .γ; entre estados, [A -> α.Xβ, a] leva
+    // a previsão a para [A -> αX.β, a] no destino da transição sobre X
+    pub(crate) fn calcula_previsoes_lalr(&mut self) {
+        let mut previsoes: Vec<Vec<Vec<String>>> = self.estados
+            .iter()
+            .map(|e| vec![Vec::new(); e.itens.len()])
+            .collect();
+        if !previsoes.is_empty() && !previsoes[0].is_empty() {
+            previsoes[0][0].push(FIM_DA_ENTRADA.to_string());
+        }
+
+        let desvios = self.obtem_desvios();
+
+        let mut mudou = true;
+        while mudou {
+            mudou = false;
+            for estado in 0..self.estados.len() {
+                for indice in 0..self.estados[estado].itens.len() {
+                    let item = self.estados[estado].itens[indice].clone();
+                    let producao = &self.gramatica.regras[item.producao].producao;
+                    if item.posicao_do_ponto >= producao.len() {
+                        continue;
+                    }
+                    let simbolo = &producao[item.posicao_do_ponto];
+                    let previsoes_do_item = previsoes[estado][indice].clone();
+
+                    // propagação para o estado destino
+                    if let Some((_, destino)) = desvios[estado].iter().find(|(s, _)| s == simbolo) {
+                        let avancado = ItemLR {
+                            producao: item.producao,
+                            posicao_do_ponto: item.posicao_do_ponto + 1,
+                        };
+                        if let Some(i) = self.estados[*destino].itens.iter().position(|i| *i == avancado) {
+                            for terminal in previsoes_do_item.iter() {
+                                mudou |= adiciona(&mut previsoes[*destino][i], terminal.to_string());
+                            }
+                        }
+                    }
+
+                    // geração espontânea dentro do próprio estado
+                    if self.gramatica.nao_terminais.contains(simbolo) {
+                        let (mut geradas, anulavel) = self.conjuntos.primeiros_da_sequencia(
+                            &self.gramatica,
+                            &producao[item.posicao_do_ponto + 1..],
+                        );
+                        if anulavel {
+                            geradas.extend(previsoes_do_item);
+                        }
+                        for (i, outro) in self.estados[estado].itens.iter().enumerate() {
+                            if outro.posicao_do_ponto == 0 && self.gramatica.regras[outro.producao].nao_terminal == *simbolo {
+                                for terminal in geradas.iter() {
+                                    mudou |= adiciona(&mut previsoes[estado][i], terminal.to_string());
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        self.previsoes = previsoes;
+    }
+
+    // transições de cada estado como pares (símbolo, estado destino)
+    pub(crate) fn obtem_desvios(&self) -> Vec<Vec<(String, usize)>> {
+        self.estados
+            .iter()
+            .map(|e| e.transicoes
+                .iter()
+                .map(|t| (
+                    self.transicoes[*t].simbolo.to_string(),
+                    self.obtem_estado(self.transicoes[*t].clone()),
+                ))
+                .collect()
+            )
+            .collect()
+    }
+}
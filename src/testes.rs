@@ -0,0 +1,832 @@
+use crate::leitor::{formata_aviso, formata_erro, ErroDeLeitura};
+use crate::{obtem_gramatica, Acao, Algoritmo, Automato, Erro};
+
+fn gera_automato(texto: &str, algoritmo: Algoritmo) -> Automato {
+    Automato::constroi(obtem_gramatica(texto.split('\n').collect()).unwrap(), algoritmo).unwrap()
+}
+
+// estado alcançado a partir de I0 ao seguir as transições sobre os símbolos
+fn percorre(automato: &Automato, simbolos: &[&str]) -> usize {
+    let desvios = automato.obtem_desvios();
+    let mut estado = 0;
+    for simbolo in simbolos {
+        estado = desvios[estado].iter().find(|(s, _)| s == simbolo).unwrap().1;
+    }
+    estado
+}
+
+fn acao(automato: &Automato, estado: usize, terminal: &str) -> String {
+    let coluna = automato.gramatica.terminais
+        .iter()
+        .position(|t| t == terminal)
+        .unwrap_or(automato.gramatica.terminais.len());
+    automato.tabela_de_acoes.get(estado, coluna).unwrap().to_string()
+}
+
+const ALGORITMOS: [Algoritmo; 4] = [Algoritmo::Slr, Algoritmo::Lalr, Algoritmo::Lr1, Algoritmo::Pager];
+
+#[test]
+fn reduz_pela_regra_do_item_com_lado_direito_repetido() {
+    let texto = "S -> Set J\nS -> Scan G\nG -> IdDeVariavel(_) PontoEVirgula\nJ -> IdDeVariavel(_) PontoEVirgula";
+    for algoritmo in ALGORITMOS {
+        let automato = gera_automato(texto, algoritmo);
+        let apos_set = percorre(&automato, &["Set", "IdDeVariavel(_)", "PontoEVirgula"]);
+        let apos_scan = percorre(&automato, &["Scan", "IdDeVariavel(_)", "PontoEVirgula"]);
+        assert_eq!(acao(&automato, apos_set, "$"), "R4");
+        assert_eq!(acao(&automato, apos_scan, "$"), "R3");
+        assert!(automato.conflitos.is_empty());
+    }
+}
+
+#[test]
+fn conjuntos_e_reducoes_so_sob_os_seguintes() {
+    let gramatica = obtem_gramatica(vec!["S -> A B c", "A -> a | ε", "B -> b | ε"]).unwrap();
+    let conjuntos = gramatica.calcula_conjuntos();
+    let [s, a, b] = ["S", "A", "B"].map(|n| gramatica.indice_nao_terminal(n).unwrap());
+    assert_eq!((conjuntos.anulaveis[s], conjuntos.anulaveis[a], conjuntos.anulaveis[b]), (false, true, true));
+    assert_eq!(conjuntos.primeiros[s], ["a", "b", "c"]);
+    assert_eq!(conjuntos.primeiros[a], ["a"]);
+    assert_eq!(conjuntos.seguintes[s], ["$"]);
+    assert_eq!(conjuntos.seguintes[a], ["b", "c"]);
+    assert_eq!(conjuntos.seguintes[b], ["c"]);
+
+    // no LR(0), T -> F reduziria sob todas as colunas; no SLR(1), só sob
+    // FOLLOW(T) = { Mult, FechaP, $ }
+    let automato = gera_automato(include_str!("../exemplo-slide.txt"), Algoritmo::Slr);
+    let apos_f = percorre(&automato, &["F"]);
+    for (terminal, esperada) in [("Mult", "R1"), ("FechaP", "R1"), ("$", "R1"), ("Id", "erro"), ("AbreP", "erro")] {
+        assert_eq!(acao(&automato, apos_f, terminal), esperada);
+    }
+    assert!(automato.conflitos.is_empty());
+}
+
+#[test]
+fn transicoes_sobre_o_mesmo_simbolo_viram_uma_so() {
+    // três itens seguidos com o a marcado: a junção não pode pular o terceiro
+    let automato = gera_automato("S -> a b | a c | a d", Algoritmo::Slr);
+    let desvios = automato.obtem_desvios();
+    assert_eq!(desvios[0].iter().filter(|(simbolo, _)| simbolo == "a").count(), 1);
+    assert_eq!(automato.estados[percorre(&automato, &["a"])].itens_iniciais.len(), 3);
+    assert_eq!(automato.estados.len(), 6);
+}
+
+#[test]
+fn previsoes_lalr_separam_o_que_o_follow_junta() {
+    // Igual está em FOLLOW(R), mas não segue R -> L. no estado após o L
+    let texto = "S -> L Igual R | R\nL -> Vezes R | Id\nR -> L";
+    let slr = gera_automato(texto, Algoritmo::Slr);
+    assert_eq!(slr.conflitos.len(), 1);
+    assert_eq!(slr.conflitos[0].simbolo, "Igual");
+
+    let lalr = gera_automato(texto, Algoritmo::Lalr);
+    assert!(lalr.conflitos.is_empty());
+    assert_eq!(lalr.estados.len(), slr.estados.len());
+    let apos_l = percorre(&lalr, &["L"]);
+    assert_eq!(acao(&lalr, apos_l, "Igual"), format!("I{}", percorre(&lalr, &["L", "Igual"])));
+    assert_eq!(acao(&lalr, apos_l, "$"), "R5");
+    let item = lalr.estados[apos_l].itens.iter().position(|i| i.producao == 5).unwrap();
+    assert_eq!(lalr.previsoes[apos_l][item], ["$"]);
+
+    // no estado após Vezes, o mesmo R -> L. tem Igual e $ como previsão
+    let apos_vezes_l = percorre(&lalr, &["Vezes", "L"]);
+    assert_eq!(acao(&lalr, apos_vezes_l, "Igual"), "R5");
+    assert_eq!(acao(&lalr, apos_vezes_l, "$"), "R5");
+}
+
+#[test]
+fn lr1_canonico_separa_estados_pela_previsao() {
+    let texto = "S -> C C\nC -> c C | d";
+    assert_eq!(gera_automato(texto, Algoritmo::Lalr).estados.len(), 7);
+
+    let automato = gera_automato(texto, Algoritmo::Lr1);
+    assert_eq!(automato.estados.len(), 10);
+    assert!(automato.conflitos.is_empty());
+    // C -> d. reduz sob c e d antes do primeiro C, e só sob $ depois dele
+    let primeiro_d = percorre(&automato, &["d"]);
+    let segundo_d = percorre(&automato, &["C", "d"]);
+    assert_ne!(primeiro_d, segundo_d);
+    for (terminal, primeiro, segundo) in [("c", "R3", "erro"), ("d", "R3", "erro"), ("$", "erro", "R3")] {
+        assert_eq!(acao(&automato, primeiro_d, terminal), primeiro);
+        assert_eq!(acao(&automato, segundo_d, terminal), segundo);
+    }
+    assert_ne!(percorre(&automato, &["c", "c"]), percorre(&automato, &["C", "c"]));
+    assert_eq!(percorre(&automato, &["c", "c"]), percorre(&automato, &["c"]));
+}
+
+#[test]
+fn nucleos_com_itens_em_outra_ordem_sao_o_mesmo_estado() {
+    // após u, o fechamento traz C -> .a b antes de D -> .a c; após v, depois
+    let texto = "S -> u X | v Y\nX -> C | D\nY -> D | C\nC -> a b\nD -> a c";
+    for algoritmo in [Algoritmo::Slr, Algoritmo::Lalr] {
+        let automato = gera_automato(texto, algoritmo);
+        assert_eq!(percorre(&automato, &["u", "a"]), percorre(&automato, &["v", "a"]));
+        assert_eq!(automato.estados.len(), 13);
+        assert!(automato.conflitos.is_empty());
+    }
+}
+
+#[test]
+fn pager_divide_so_os_nucleos_que_geram_conflito() {
+    // o LR(1) canônico de S -> C C tem 10 estados; sem conflitos, o Pager junta
+    // todos os de mesmo núcleo, como o LALR(1)
+    let automato = gera_automato("S -> C C\nC -> c C | d", Algoritmo::Pager);
+    assert_eq!(automato.estados.len(), 7);
+    assert_eq!(automato.resumo_da_mesclagem(), "7 estados, 7 no LALR(1): 0 núcleo(s) dividido(s), 0 estado(s) a mais\n");
+
+    // o estado após c junta A -> c. e B -> c. com previsões trocadas após a e
+    // após b; juntá-los geraria o conflito do LALR(1)
+    let automato = gera_automato("S -> a A d | b B d | a B e | b A e\nA -> c\nB -> c", Algoritmo::Pager);
+    assert_eq!(automato.estados.len(), 14);
+    assert!(automato.conflitos.is_empty());
+    let (apos_a, apos_b) = (percorre(&automato, &["a", "c"]), percorre(&automato, &["b", "c"]));
+    assert_eq!(
+        automato.resumo_da_mesclagem(),
+        format!(
+            "14 estados, 13 no LALR(1): 1 núcleo(s) dividido(s), 1 estado(s) a mais\n  núcleo dividido em I{}, I{}\n",
+            apos_a.min(apos_b), apos_a.max(apos_b),
+        ),
+    );
+}
+
+#[test]
+fn regras_com_lado_direito_repetido_no_mesmo_estado_geram_conflito() {
+    // A -> c e B -> c chegam juntas ao mesmo estado; só a previsão as separa
+    let texto = "S -> a A d\nS -> b B d\nS -> a B e\nS -> b A e\nA -> c\nB -> c";
+
+    let automato = gera_automato(texto, Algoritmo::Lalr);
+    assert_eq!(automato.conflitos.len(), 2);
+    assert!(automato.conflitos.iter().all(|c| c.acoes.iter().map(|a| a.0).eq([Acao::Reduz(5), Acao::Reduz(6)])));
+
+    for algoritmo in [Algoritmo::Lr1, Algoritmo::Pager] {
+        let automato = gera_automato(texto, algoritmo);
+        assert!(automato.conflitos.is_empty());
+        assert_eq!(acao(&automato, percorre(&automato, &["a", "c"]), "d"), "R5");
+        assert_eq!(acao(&automato, percorre(&automato, &["a", "c"]), "e"), "R6");
+        assert_eq!(acao(&automato, percorre(&automato, &["b", "c"]), "d"), "R6");
+        assert_eq!(acao(&automato, percorre(&automato, &["b", "c"]), "e"), "R5");
+    }
+}
+
+#[test]
+fn gramatica_lia_reduz_j_e_g_pelas_proprias_regras() {
+    // R11 é G -> IdDeVariavel(_) PontoEVirgula e R23 é J -> IdDeVariavel(_) PontoEVirgula
+    let texto = include_str!("../gramatica-lia.txt");
+    for algoritmo in ALGORITMOS {
+        let automato = gera_automato(texto, algoritmo);
+        let declaracao = percorre(&automato, &["AbreBlocoDATA", "TipoDeVariavel(_)", "DoisPontos", "IdDeVariavel(_)", "PontoEVirgula"]);
+        let atribuicao = percorre(&automato, &["A", "AbreBlocoMAIN", "Set", "DoisPontos", "IdDeVariavel(_)", "Virgula", "IdDeVariavel(_)", "PontoEVirgula"]);
+        assert_eq!(acao(&automato, declaracao, "FechaBlocoDATA"), "R11");
+        assert_eq!(acao(&automato, atribuicao, "FechaBlocoMAIN"), "R23");
+        assert!(automato.conflitos.is_empty());
+    }
+}
+
+#[test]
+fn producao_vazia_reduz_sob_os_seguintes() {
+    let texto = "S -> A B\nA -> Abre\nB -> ε\nB -> C\nC -> Fecha";
+    for algoritmo in ALGORITMOS {
+        let automato = gera_automato(texto, algoritmo);
+        assert!(automato.gramatica.regras[3].producao.is_empty());
+        assert!(!automato.gramatica.terminais.iter().any(|t| t == "ε"));
+        let apos_a = percorre(&automato, &["A"]);
+        assert_eq!(acao(&automato, apos_a, "$"), "R3");
+        assert_eq!(acao(&automato, apos_a, "Fecha"), format!("I{}", percorre(&automato, &["A", "Fecha"])));
+        assert!(automato.conflitos.is_empty());
+    }
+}
+
+#[test]
+fn alternativa_vazia_precisa_do_marcador_sozinho() {
+    let erros = obtem_gramatica(vec!["S -> a A | B", "A ->", "B -> a |", "  | %empty", "C -> a ε b", "D -> ( a | )? x"]).unwrap_err();
+    let posicoes: Vec<(usize, usize, &str)> = erros.iter().map(|e| (e.linha, e.coluna, e.mensagem.as_str())).collect();
+    assert_eq!(posicoes, vec![
+        (2, 5, "esperado um símbolo, ou ε para uma alternativa vazia"),
+        (3, 9, "esperado um símbolo, ou ε para uma alternativa vazia"),
+        (5, 8, "esperado ε sozinho na alternativa, encontrado junto de outros símbolos"),
+        (6, 12, "esperado um símbolo, ou ε para uma alternativa vazia"),
+    ]);
+
+    // o %prec não conta como símbolo ao lado do marcador
+    let gramatica = obtem_gramatica(vec!["%left X", "S -> a S | ε %prec X"]).unwrap();
+    assert_eq!(gramatica.regras[2].precedencia.as_deref(), Some("X"));
+    assert!(gramatica.regras[2].producao.is_empty());
+}
+
+#[test]
+fn alternativas_e_linhas_de_continuacao_viram_regras_numeradas_em_ordem() {
+    let separadas = obtem_gramatica(include_str!("../exemplo-slide.txt").split('\n').collect()).unwrap();
+    let agrupadas = obtem_gramatica(vec!["T->F|T  Mult F", "F\t->  Id", "   | AbreP T FechaP"]).unwrap();
+    assert_eq!(agrupadas.regras.len(), separadas.regras.len());
+    for (a, b) in agrupadas.regras.iter().zip(separadas.regras.iter()) {
+        assert_eq!(a.nao_terminal, b.nao_terminal);
+        assert_eq!(a.producao, b.producao);
+    }
+    assert_eq!(agrupadas.terminais, separadas.terminais);
+}
+
+#[test]
+fn operadores_ebnf_viram_nao_terminais_auxiliares_no_fim() {
+    let gramatica = obtem_gramatica(vec![
+        "S -> A? ( Virgula A )* Fim",
+        "A -> ( Id | Num )+ | ( A )",
+        "S_1 -> Id",
+    ]).unwrap();
+    let regras: Vec<(&str, Vec<&str>)> = gramatica.regras
+        .iter()
+        .map(|r| (r.nao_terminal.as_str(), r.producao.iter().map(|s| s.as_str()).collect()))
+        .collect();
+    assert_eq!(regras, vec![
+        ("$inicio", vec!["S"]),
+        ("S", vec!["S_2", "S_3", "Fim"]),
+        ("A", vec!["A_2"]),
+        ("A", vec!["(", "A", ")"]),
+        ("S_1", vec!["Id"]),
+        ("S_2", vec!["A"]),
+        ("S_2", vec![]),
+        ("S_3", vec![]),
+        ("S_3", vec!["S_3", "Virgula", "A"]),
+        ("A_1", vec!["Id"]),
+        ("A_1", vec!["Num"]),
+        ("A_2", vec!["A_1"]),
+        ("A_2", vec!["A_2", "A_1"]),
+    ]);
+}
+
+#[test]
+fn operadores_repetidos_e_literais_entre_aspas_sao_simbolos() {
+    // ++ e C++ não têm um símbolo antes do operador; entre aspas nada é operador
+    let gramatica = obtem_gramatica(vec!["S -> C++ ++ ** Id+ '+'? 'a*' '|' '->' 'ε'"]).unwrap();
+    assert_eq!(regras_de(&gramatica), vec![
+        ("S".to_string(), "C++ ++ ** S_1 S_2 'a*' '|' '->' 'ε'".to_string()),
+        ("S_1".to_string(), "Id".to_string()),
+        ("S_1".to_string(), "S_1 Id".to_string()),
+        ("S_2".to_string(), "'+'".to_string()),
+        ("S_2".to_string(), "".to_string()),
+    ]);
+    assert_eq!(gramatica.identificadores().unwrap().terminais, vec![
+        "CMaisMais", "MaisMais", "AsteriscoAsterisco", "AAsterisco", "BarraVertical", "MenosMaior", "U03B5", "Id",
+        "Mais",
+    ]);
+}
+
+#[test]
+fn erros_de_leitura_sao_coletados_com_linha_e_coluna() {
+    let linhas = vec!["E -> T", "T F", "T -> a -> b", "F -> x )+", "AB B -> c"];
+    let erros = obtem_gramatica(linhas.to_vec()).unwrap_err();
+    let posicoes: Vec<(usize, usize)> = erros.iter().map(|e| (e.linha, e.coluna)).collect();
+    assert_eq!(posicoes, vec![(2, 2), (3, 8), (4, 8), (5, 4)]);
+    assert_eq!(
+        formata_erro("g.txt", &linhas, &erros[0]),
+        "g.txt:2:2: erro: esperado -> após o não terminal\n  |\n2 | T F\n  |  ^",
+    );
+    assert_eq!(obtem_gramatica(vec!["", "# sem regras"]).unwrap_err(), vec![ErroDeLeitura {
+        linha: 1,
+        coluna: 1,
+        mensagem: "esperada ao menos uma regra de produção".to_string(),
+    }]);
+}
+
+#[test]
+fn comentarios_linhas_em_branco_e_crlf_nao_alteram_a_gramatica() {
+    let original = obtem_gramatica(include_str!("../exemplo-slide.txt").split('\n').collect()).unwrap();
+    let texto = "# gramática do slide\r\n\r\nT -> F // fator\r\n  \r\nT -> T Mult F # comentário\r\nF -> Id\r\n// parênteses\r\nF -> AbreP T FechaP\r\n";
+    let anotada = obtem_gramatica(texto.split('\n').collect()).unwrap();
+    assert_eq!(anotada.nao_terminais, original.nao_terminais);
+    for (a, b) in anotada.regras.iter().zip(original.regras.iter()) {
+        assert_eq!(a.producao, b.producao);
+    }
+    assert_eq!(anotada.terminais, original.terminais);
+    assert!(!anotada.terminais.iter().any(|t| t.contains('\r')));
+
+    // # colado a um símbolo faz parte dele
+    let gramatica = obtem_gramatica(vec!["S -> C# Id"]).unwrap();
+    assert_eq!(gramatica.terminais, vec!["C#", "Id"]);
+}
+
+#[test]
+fn gramatica_e_aumentada_a_partir_do_simbolo_inicial() {
+    let gramatica = obtem_gramatica(vec!["T -> F", "%start E", "E -> T", "F -> Id"]).unwrap();
+    assert_eq!(gramatica.regras[0].nao_terminal, "$inicio");
+    assert_eq!(gramatica.regras[0].producao, vec!["E"]);
+    assert_eq!(gramatica.regras[1].nao_terminal, "T");
+
+    let automato = gera_automato("T -> F\nF -> Id", Algoritmo::Lalr);
+    assert_eq!(acao(&automato, percorre(&automato, &["T"]), "$"), "ACEITAR");
+
+    let linhas = vec!["%start X", "E -> $ Id", "%start E", "%inicio Id"];
+    let erros = obtem_gramatica(linhas).unwrap_err();
+    let posicoes: Vec<(usize, usize)> = erros.iter().map(|e| (e.linha, e.coluna)).collect();
+    assert_eq!(posicoes, vec![(2, 6), (3, 1), (4, 1), (1, 8)]);
+}
+
+#[test]
+fn simbolos_fora_da_secao_token_sao_erros() {
+    let linhas = ["%token Id AbreP FechaP Mult", "T -> F | T Mult F", "F -> Id", "   | AbreP T FechaPar"];
+    let erros = obtem_gramatica(linhas.to_vec()).unwrap_err();
+    assert_eq!(erros.len(), 1);
+    assert_eq!((erros[0].linha, erros[0].coluna), (4, 14));
+    assert!(erros[0].mensagem.contains("FechaPar"));
+
+    let gramatica = obtem_gramatica(vec!["%token Id Num", "%token Mais", "E -> E Mais Id | Id"]).unwrap();
+    assert_eq!(gramatica.terminais, vec!["Mais", "Id"]);
+    let avisos: Vec<(usize, usize)> = gramatica.avisos.iter().map(|a| (a.linha, a.coluna)).collect();
+    assert_eq!(avisos, vec![(1, 11)]);
+    assert_eq!(
+        formata_aviso("g.txt", &["%token Id Num"], &gramatica.avisos[0]),
+        "g.txt:1:11: aviso: token Num declarado mas não usado em nenhuma regra\n  |\n1 | %token Id Num\n  |           ^",
+    );
+
+    // sem seção %token, qualquer símbolo sem regras continua sendo terminal
+    assert!(obtem_gramatica(vec!["F -> AbreP F FechaPar | Id"]).unwrap().avisos.is_empty());
+}
+
+#[test]
+fn precedencias_resolvem_conflitos_empilha_reduz() {
+    let texto = "%left Mais\n%left Mult\n%right Pot\n%nonassoc Menor\nE -> E Mais E | E Mult E | E Pot E | E Menor E\n  | Menos E %prec Pot\n  | Id";
+    for algoritmo in ALGORITMOS {
+        let automato = gera_automato(texto, algoritmo);
+        assert!(automato.conflitos.is_empty());
+        assert!(!automato.conflitos_resolvidos.is_empty());
+
+        let soma = percorre(&automato, &["E", "Mais", "E"]);
+        assert_eq!(acao(&automato, soma, "Mais"), "R1");
+        assert!(acao(&automato, soma, "Mult").starts_with('I'));
+        let produto = percorre(&automato, &["E", "Mult", "E"]);
+        assert_eq!(acao(&automato, produto, "Mais"), "R2");
+        let potencia = percorre(&automato, &["E", "Pot", "E"]);
+        assert!(acao(&automato, potencia, "Pot").starts_with('I'));
+        let comparacao = percorre(&automato, &["E", "Menor", "E"]);
+        assert_eq!(acao(&automato, comparacao, "Menor"), "erro");
+        // o %prec dá a Menos E a precedência de Pot, acima de Mult
+        let negacao = percorre(&automato, &["Menos", "E"]);
+        assert_eq!(acao(&automato, negacao, "Mult"), "R5");
+    }
+
+    // sem precedência para Mult, só os conflitos sobre Mais são resolvidos
+    let automato = gera_automato("%left Mais\nE -> E Mais E | E Mult E | Id", Algoritmo::Lalr);
+    assert_eq!(automato.conflitos.len(), 3);
+    assert_eq!(automato.conflitos_resolvidos.len(), 1);
+
+    let erros = obtem_gramatica(vec!["%left Mais", "E -> Id %prec", "E -> ( Id %prec Mais )? | Id", "E -> E Mais E %prec Menos"]).unwrap_err();
+    let posicoes: Vec<(usize, usize)> = erros.iter().map(|e| (e.linha, e.coluna)).collect();
+    assert_eq!(posicoes, vec![(2, 9), (3, 11), (4, 15)]);
+}
+
+#[test]
+fn nao_terminais_inuteis_geram_avisos_com_as_linhas_das_regras() {
+    let gramatica = obtem_gramatica(vec!["S -> A | B", "A -> Id", "", "B -> B Mais C", "C -> Id", "D -> Num", "  | D+"]).unwrap();
+    let avisos: Vec<(usize, &str)> = gramatica.avisos.iter().map(|a| (a.linha, a.mensagem.as_str())).collect();
+    assert_eq!(avisos, vec![
+        (4, "não terminal B não deriva nenhuma sequência de terminais; regra(s) nunca usada(s): R4 (linha 4)"),
+        (5, "não terminal C não é alcançável a partir do símbolo inicial; regra(s) nunca usada(s): R5 (linha 5)"),
+        (6, "não terminal D não é alcançável a partir do símbolo inicial; regra(s) nunca usada(s): R6 (linha 6), R7 (linha 7)"),
+        (7, "não terminal D_1 não é alcançável a partir do símbolo inicial; regra(s) nunca usada(s): R8 (linha 7), R9 (linha 7)"),
+        (1, "a regra R2 (linha 1) nunca é usada, B não deriva nenhuma sequência de terminais"),
+    ]);
+    assert!(obtem_gramatica(include_str!("../gramatica-lia.txt").split('\n').collect()).unwrap().avisos.is_empty());
+}
+
+#[test]
+fn simbolos_com_nome_de_nao_terminal_precisam_de_regras() {
+    let linhas = vec!["%nonterminals [A-Z]", "E -> E Mais T | T", "T -> Id | AbreP E M"];
+    let erros = obtem_gramatica(linhas).unwrap_err();
+    let posicoes: Vec<(usize, usize)> = erros.iter().map(|e| (e.linha, e.coluna)).collect();
+    assert_eq!(posicoes, vec![(3, 19)]);
+
+    // um token declarado pode seguir a convenção
+    assert!(obtem_gramatica(vec!["%nonterminals [A-Z]", "%token Id M", "E -> Id M"]).is_ok());
+    assert!(obtem_gramatica(vec!["%nonterminals ^[A-Z][a-z_0-9]*$", "Expr -> Termo", "Termo -> id", "  | ( Expr )"]).is_ok());
+
+    let erros = obtem_gramatica(vec!["%nonterminals [Z-A]", "%nonterminals [A-Z]", "%nonterminals A", "E -> Id"]).unwrap_err();
+    let posicoes: Vec<(usize, usize)> = erros.iter().map(|e| (e.linha, e.coluna)).collect();
+    assert_eq!(posicoes, vec![(1, 15), (3, 1)]);
+
+    // alternativas, grupos e repetições contadas não são caracteres literais
+    for padrao in ["(Expr|Termo)", "[A-Z][a-z]{1,3}"] {
+        let erros = obtem_gramatica(vec![&format!("%nonterminals {}", padrao), "E -> Id"]).unwrap_err();
+        assert_eq!((erros.len(), erros[0].linha, erros[0].coluna), (1, 1, 15));
+        assert!(erros[0].mensagem.starts_with("padrão inválido: "));
+    }
+    assert_eq!(
+        crate::padrao::Padrao::novo("(Expr|Termo)").unwrap_err(),
+        "( não é suportado; use \\( para o caractere",
+    );
+    assert!(crate::padrao::Padrao::novo("Expr\\(\\)").unwrap().casa("Expr()"));
+}
+
+#[test]
+fn padrao_casa_o_simbolo_inteiro() {
+    use crate::padrao::Padrao;
+    let padrao = Padrao::novo("[A-Z][A-Za-z]*\\'?").unwrap();
+    assert!(padrao.casa("E"));
+    assert!(padrao.casa("Expr'"));
+    assert!(!padrao.casa("expr"));
+    assert!(!padrao.casa("Expr''"));
+    let padrao = Padrao::novo("[^a-z].+_?").unwrap();
+    assert!(padrao.casa("E_1_"));
+    assert!(!padrao.casa("E"));
+    assert!(Padrao::novo("[A-Z").is_err());
+    assert!(Padrao::novo("*A").is_err());
+}
+
+fn regras_de(gramatica: &crate::Gramatica) -> Vec<(String, String)> {
+    gramatica.regras.iter().skip(1).map(|r| (r.nao_terminal.to_string(), r.producao.join(" "))).collect()
+}
+
+#[test]
+fn recursao_a_esquerda_direta_e_indireta_e_removida() {
+    let gramatica = obtem_gramatica(vec!["S -> A a | b", "A -> A c | S d | ε"]).unwrap();
+    let transformada = gramatica.remove_recursao_a_esquerda();
+    assert_eq!(
+        transformada.escreve(),
+        "S -> A a\n  | b\nA -> b d A_1\n  | A_1\nA_1 -> c A_1\n    | a d A_1\n    | ε\n",
+    );
+
+    // o texto escrito é lido de volta com as mesmas regras
+    let relida = obtem_gramatica(transformada.escreve().split('\n').collect()).unwrap();
+    assert_eq!(regras_de(&relida), regras_de(&transformada));
+}
+
+#[test]
+fn prefixos_comuns_sao_fatorados() {
+    let gramatica = obtem_gramatica(vec![
+        "S -> A B | A B C | D",
+        "I -> Print DoisPontos String PontoEVirgula",
+        "  | Print DoisPontos String Virgula J",
+        "  | Print Fim",
+    ]).unwrap();
+    let fatorada = gramatica.fatora_a_esquerda();
+    assert_eq!(regras_de(&fatorada), [
+        ("S", "A B S_1"),
+        ("S", "D"),
+        ("S_1", ""),
+        ("S_1", "C"),
+        ("I", "Print I_1"),
+        ("I_1", "DoisPontos String I_2"),
+        ("I_1", "Fim"),
+        ("I_2", "PontoEVirgula"),
+        ("I_2", "Virgula J"),
+    ].map(|(a, b)| (a.to_string(), b.to_string())));
+
+    // depois de fatorada e sem recursão à esquerda, a gramática do slide não
+    // tem mais alternativas que comecem pelo mesmo símbolo
+    let slide = obtem_gramatica(include_str!("../exemplo-slide.txt").split('\n').collect()).unwrap();
+    let transformada = slide.remove_recursao_a_esquerda().fatora_a_esquerda();
+    for nao_terminal in transformada.nao_terminais.iter() {
+        let primeiros: Vec<Option<&String>> = transformada.regras
+            .iter()
+            .filter(|r| r.nao_terminal == *nao_terminal)
+            .map(|r| r.producao.first())
+            .collect();
+        assert!(primeiros.iter().enumerate().all(|(i, p)| p.is_none() || !primeiros[i + 1..].contains(p)));
+    }
+}
+
+#[test]
+fn transformacoes_preservam_declaracoes_e_precedencias() {
+    let senao = obtem_gramatica(vec![
+        "%token If E Then Outro",
+        "%nonassoc Then",
+        "%nonassoc Else",
+        "S -> If E Then S %prec Then | If E Then S Else S | Outro",
+    ]).unwrap();
+    let expressoes = obtem_gramatica(vec![
+        "S -> E",
+        "%token Id Menos",
+        "%left Mais",
+        "%left Vezes",
+        "%right Unario",
+        "E -> E Mais E | E Vezes E | Menos E %prec Unario | Id",
+    ]).unwrap();
+    let fatorada = senao.fatora_a_esquerda();
+    assert_eq!(
+        fatorada.escreve(),
+        "%token If E Then Outro\n%nonassoc Then\n%nonassoc Else\n\
+         S -> If E Then S S_1\n  | Outro\nS_1 -> ε %prec Then\n    | Else S\n",
+    );
+    let sem_recursao = expressoes.remove_recursao_a_esquerda();
+    assert!(sem_recursao.escreve().starts_with("%token Id Menos\n%left Mais\n%left Vezes\n%right Unario\nS -> E\n"));
+    assert!(sem_recursao.escreve().contains("E -> Menos E E_1 %prec Unario\n"));
+
+    // relida, a gramática transformada gera as mesmas tabelas e conflitos
+    for transformada in [fatorada, sem_recursao] {
+        let relida = obtem_gramatica(transformada.escreve().split('\n').collect()).unwrap();
+        let original = Automato::constroi(transformada, Algoritmo::Lalr).unwrap();
+        let relido = Automato::constroi(relida, Algoritmo::Lalr).unwrap();
+        assert_eq!(relido.tabela_md(), original.tabela_md());
+        assert_eq!(relido.formata_conflitos(false), original.formata_conflitos(false));
+    }
+
+    // o Else é empilhado pela precedência herdada por S_1 -> ε
+    let automato = Automato::constroi(senao.fatora_a_esquerda(), Algoritmo::Lalr).unwrap();
+    assert!(automato.conflitos.is_empty());
+    assert_eq!(acao(&automato, percorre(&automato, &["If", "E", "Then", "S"]), "Else"), "I7");
+}
+
+#[test]
+fn tabela_ll1_e_conflitos_first_first_e_first_follow() {
+    let slide = obtem_gramatica(include_str!("../exemplo-slide.txt").split('\n').collect()).unwrap();
+    let analise = slide.remove_recursao_a_esquerda().analisa_ll1();
+    assert!(analise.conflitos.is_empty());
+    let t_1 = analise.gramatica.indice_nao_terminal("T_1").unwrap();
+    let celulas: Vec<Vec<usize>> = analise.tabela[t_1].iter().map(|c| c.iter().map(|(r, _)| *r).collect()).collect();
+    // colunas: Mult, Id, AbreP, FechaP, $
+    assert_eq!(celulas, vec![vec![2], vec![], vec![], vec![3], vec![3]]);
+
+    let analise = slide.analisa_ll1();
+    assert_eq!(analise.conflitos.len(), 2);
+    assert!(analise.conflitos.iter().all(|c| c.nao_terminal == "T" && c.regras == vec![(1, false), (2, false)]));
+
+    // o else pendente: Else está em FIRST de S_1 -> Else S e em FOLLOW(S_1)
+    let analise = obtem_gramatica(vec!["S -> If S S_1 | Outro", "S_1 -> Else S | ε"]).unwrap().analisa_ll1();
+    assert_eq!(analise.conflitos.len(), 1);
+    assert_eq!(analise.conflitos[0].simbolo, "Else");
+    assert_eq!(analise.conflitos[0].regras, vec![(3, false), (4, true)]);
+}
+
+#[test]
+fn relatorio_dos_conjuntos_em_texto_e_markdown() {
+    let gramatica = obtem_gramatica(vec!["S -> A B", "A -> Abre | ε", "B -> Fecha"]).unwrap();
+    let conjuntos = gramatica.calcula_conjuntos();
+    assert_eq!(
+        conjuntos.formata_texto(&gramatica),
+        "  S: anulável: não, FIRST = {Abre, Fecha}, FOLLOW = {$}\n\
+         \x20 A: anulável: sim, FIRST = {Abre}, FOLLOW = {Fecha}\n\
+         \x20 B: anulável: não, FIRST = {Fecha}, FOLLOW = {$}\n",
+    );
+    assert_eq!(
+        conjuntos.formata_md(&gramatica),
+        "| Não terminal | Anulável | FIRST | FOLLOW |\n|---|---|---|---|\n\
+         | S | não | Abre, Fecha | $ |\n\
+         | A | sim | Abre | Fecha |\n\
+         | B | não | Fecha | $ |\n",
+    );
+}
+
+#[test]
+fn conflitos_trazem_contraexemplos() {
+    // gramática ambígua: a mesma entrada tem duas árvores
+    let automato = gera_automato("E -> E Mais E | Id", Algoritmo::Slr);
+    assert_eq!(automato.conflitos.len(), 1);
+    let contraexemplo = automato.contraexemplo(&automato.conflitos[0]);
+    assert_eq!(contraexemplo.caminho, vec!["E", "Mais", "E"]);
+    let (sentenca, primeira, segunda) = contraexemplo.derivacoes.unwrap();
+    assert_eq!(sentenca, vec!["Id", "Mais", "Id", "Mais", "Id"]);
+    let mut arvores = vec![primeira, segunda];
+    arvores.sort();
+    assert_eq!(arvores, vec![
+        "E[ E[ E[ Id ] Mais E[ Id ] ] Mais E[ Id ] ]",
+        "E[ E[ Id ] Mais E[ E[ Id ] Mais E[ Id ] ] ]",
+    ]);
+
+    // conflito só do LALR(1): cada ação tem a sua entrada, sem ambiguidade
+    let automato = gera_automato("S -> a A d | b B d | a B e | b A e\nA -> c\nB -> c", Algoritmo::Lalr);
+    let conflito = automato.conflitos.iter().find(|c| c.simbolo == "d").unwrap();
+    let contraexemplo = automato.contraexemplo(conflito);
+    assert!(contraexemplo.derivacoes.is_none());
+    let exemplos: Vec<(Acao, Vec<String>, usize)> = contraexemplo.exemplos
+        .into_iter()
+        .map(|(acao, exemplo)| {
+            let (sentenca, marcador) = exemplo.unwrap();
+            (acao, sentenca, marcador)
+        })
+        .collect();
+    assert_eq!(exemplos, vec![
+        (Acao::Reduz(5), vec!["a".to_string(), "c".to_string(), "d".to_string()], 2),
+        (Acao::Reduz(6), vec!["b".to_string(), "c".to_string(), "d".to_string()], 2),
+    ]);
+}
+
+#[test]
+fn contraexemplos_de_gramatica_ambigua_sao_encontrados() {
+    // com H -> H H, toda sequência de três comandos tem duas árvores; a entrada
+    // de cada conflito começa pelo menor caminho, com a menor sentença de cada
+    // não terminal, que passa pelo menor bloco DATA
+    let texto = std::fs::read_to_string("gramatica-lia.txt").unwrap() + "\nH -> H H";
+    for algoritmo in ALGORITMOS {
+        let automato = gera_automato(&texto, algoritmo);
+        assert!(!automato.conflitos.is_empty());
+        for contraexemplo in automato.contraexemplos() {
+            let (sentenca, primeira, segunda) = contraexemplo.derivacoes.unwrap();
+            assert_ne!(primeira, segunda);
+            assert_eq!(sentenca[..6], [
+                "AbreBlocoDATA", "TipoDeVariavel(_)", "DoisPontos", "IdDeVariavel(_)", "PontoEVirgula", "FechaBlocoDATA",
+            ]);
+        }
+    }
+
+    // os contraexemplos só são buscados quando pedidos
+    let automato = gera_automato("E -> E Mais E | Id", Algoritmo::Slr);
+    assert!(!automato.formata_conflitos(false).contains("estado alcançado por"));
+    assert!(automato.formata_conflitos(true).contains("a entrada Id Mais Id Mais Id tem duas derivações"));
+}
+
+#[test]
+fn contraexemplos_com_regras_vazias_terminam() {
+    // as reduções por S -> ε empilham sem ler entrada
+    let automato = gera_automato("S -> S S | a | ε", Algoritmo::Slr);
+    assert!(automato.formata_conflitos(true).contains(
+        "conflito empilha/reduz no estado I0 sob o símbolo a:\n  I3:\n    S -> .a\n  R3:\n    S -> .\n  \
+         ação mantida na tabela: I3\n  estado alcançado por: \n  a entrada a tem duas derivações:\n",
+    ));
+
+    let automato = gera_automato("S -> A S | a\nA -> ε", Algoritmo::Slr);
+    assert!(!automato.conflitos.is_empty());
+    assert!(automato.formata_conflitos(true).contains("estado alcançado por"));
+}
+
+#[test]
+fn api_devolve_erros_tipados() {
+    assert!(matches!(crate::le_gramatica("nao-existe.txt"), Err(Erro::Arquivo(_))));
+
+    let arquivo = crate::ArquivoDeGramatica {
+        caminho: "g.txt".to_string(),
+        conteudo: "S -> a\nA B -> c\n".to_string(),
+    };
+    let erros = arquivo.gramatica().unwrap_err();
+    assert_eq!(arquivo.formata_erro(&erros[0]), "\
+        g.txt:2:3: erro: esperado -> após o não terminal, encontrado outro símbolo\n  |\n2 | A B -> c\n  |   ^");
+
+    let gramatica = crate::le_gramatica("exemplo-slide.txt").unwrap();
+    let automato = crate::gera_automato(gramatica.clone(), Algoritmo::Slr).unwrap();
+    assert!(automato.tabela_md().starts_with("| Estado | Mult | Id | AbreP | FechaP | $ | T | F |\n"));
+
+    let gramatica = obtem_gramatica(vec!["E -> E Mais E | Id"]).unwrap();
+    match crate::gera_automato(gramatica, Algoritmo::Lalr) {
+        Err(Erro::Conflitos(conflitos)) => assert_eq!(conflitos.len(), 1),
+        _ => panic!("esperado um conflito"),
+    }
+
+    // a construção valida a gramática em vez de falhar no meio
+    let mut gramatica = obtem_gramatica(vec!["S -> a"]).unwrap();
+    gramatica.regras.remove(0);
+    gramatica.regras[0].producao.push("b".to_string());
+    match Automato::constroi(gramatica, Algoritmo::Slr) {
+        Err(Erro::GramaticaInvalida(erros)) => assert_eq!(erros, vec![
+            "esperada a regra aumentada $inicio -> S como R0, com $inicio como primeiro não terminal",
+            "o símbolo b da regra R0 não é terminal nem não terminal",
+            "o não terminal $inicio não tem regras",
+        ]),
+        _ => panic!("esperada uma gramática inválida"),
+    }
+}
+
+#[test]
+fn automato_e_lido_pelos_acessores() {
+    let automato = crate::gera_automato(obtem_gramatica(vec!["S -> a S | b"]).unwrap(), Algoritmo::Slr).unwrap();
+    let gramatica = automato.gramatica();
+    assert_eq!(gramatica.terminais(), ["a", "b"]);
+    assert_eq!(gramatica.nao_terminais(), ["$inicio", "S"]);
+    assert_eq!(gramatica.regras()[1].nao_terminal(), "S");
+    assert_eq!(gramatica.regras()[1].producao(), ["a", "S"]);
+
+    // ACTION com o $ depois do último terminal e GOTO na ordem dos não terminais
+    let apos_a = percorre(&automato, &["a"]);
+    assert_eq!(automato.acao(0, 0), Some(Acao::Empilha(apos_a)));
+    assert_eq!(automato.acao(percorre(&automato, &["S"]), 2), Some(Acao::Aceita));
+    assert_eq!(automato.acao(automato.estados().len(), 0), None);
+    assert_eq!(automato.desvio(apos_a, 1), Some(Acao::VaiPara(percorre(&automato, &["a", "S"]))));
+
+    let estado = &automato.estados()[apos_a];
+    assert_eq!(estado.itens_iniciais()[0].posicao_do_ponto(), 1);
+    let transicao = &automato.transicoes()[estado.transicoes()[0]];
+    assert_eq!(transicao.simbolo(), "S");
+    assert!(automato.conflitos().is_empty());
+}
+
+#[test]
+fn tabelas_de_acoes_e_desvios_separadas() {
+    // não terminal com nome iniciado por I e terminal com nome iniciado por R
+    let automato = gera_automato("S -> Inicio Final\nInicio -> Id Resto\nResto -> R", Algoritmo::Slr);
+    let inicio = automato.gramatica.nao_terminais.iter().position(|n| n == "Inicio").unwrap();
+    let apos_inicio = percorre(&automato, &["Inicio"]);
+    assert_eq!(automato.tabela_de_desvios.get(0, inicio), Some(&Acao::VaiPara(apos_inicio)));
+    assert_eq!(automato.tabela_de_desvios.get(0, 0), Some(&Acao::Erro));
+    assert_eq!(acao(&automato, percorre(&automato, &["Id", "R"]), "Final"), "R3");
+    assert_eq!(acao(&automato, percorre(&automato, &["Inicio", "Final"]), "$"), "R1");
+
+    let rust = automato.tabela_rust(&automato.gramatica.identificadores().unwrap());
+    assert!(rust.contains(&format!(
+        "if let ElementosDaPilha::NaoTerminais(NaoTerminais::Inicio) = simbolo {{\n                    return Ok(Acoes::VaiPara({}));",
+        apos_inicio,
+    )));
+    assert!(rust.contains("let ElementosDaPilha::Tokens(Tokens::R) = simbolo {\n                    return Ok(Acoes::Empilha("));
+}
+
+// compila com o rustc, sem avisos, os arquivos dados num diretório temporário,
+// a partir do main.rs, e devolve a saída da execução
+fn compila_e_executa(nome: &str, arquivos: &[(&str, &str)]) -> String {
+    use std::process::Command;
+
+    let diretorio = std::env::temp_dir().join(format!("slr1aux-{}-{}", nome, std::process::id()));
+    std::fs::create_dir_all(&diretorio).unwrap();
+    for (arquivo, conteudo) in arquivos {
+        std::fs::write(diretorio.join(arquivo), conteudo).unwrap();
+    }
+    let compilacao = Command::new("rustc")
+        .args(["--edition", "2021", "-D", "warnings", "-o"])
+        .arg(diretorio.join("teste"))
+        .arg(diretorio.join("main.rs"))
+        .output()
+        .unwrap();
+    assert!(compilacao.status.success(), "{}", String::from_utf8_lossy(&compilacao.stderr));
+    let execucao = Command::new(diretorio.join("teste")).output().unwrap();
+    std::fs::remove_dir_all(&diretorio).unwrap();
+    assert!(execucao.status.success());
+    String::from_utf8_lossy(&execucao.stdout).to_string()
+}
+
+#[test]
+fn modulo_rust_compila_sozinho() {
+    let automato = gera_automato("%left Mais\nE -> E Mais E | AbreP E FechaP | Id", Algoritmo::Lalr);
+    let modulo = automato.modulo_rust(&automato.gramatica.identificadores().unwrap());
+    let principal = "\
+        mod analisador;\n\
+        use analisador::Tokens::*;\n\
+        fn main() {\n\
+            println!(\"{:?}\", analisador::analisa([Id, Mais, AbreP, Id, Mais, Id, FechaP]));\n\
+            println!(\"{:?}\", analisador::analisa([Id, Mais]).unwrap_err().posicao);\n\
+        }\n";
+    let saida = compila_e_executa("modulo", &[("analisador.rs", &modulo), ("main.rs", principal)]);
+    assert_eq!(saida, "Ok([3, 3, 3, 1, 2, 1])\n2\n");
+}
+
+#[test]
+fn identificadores_dos_simbolos() {
+    let gramatica = obtem_gramatica(vec![
+        "S -> type Id(_) '+' \"if\" x-y 1 Self ( ) A",
+        "A -> ε",
+    ]).unwrap();
+    let identificadores = gramatica.identificadores().unwrap();
+    assert_eq!(identificadores.terminais, vec![
+        "Type", "Id", "Mais", "If", "XMenosy", "Simbolo1", "SimboloSelf", "AbreP", "FechaP",
+    ]);
+    assert_eq!(identificadores.padrao_do_terminal(1), "Tokens::Id(_)");
+    assert_eq!(identificadores.nao_terminais, vec!["SL", "S", "A"]);
+
+    // S' e a regra aumentada viram SL; Id(_) e Id, Tokens::Id; fim, Tokens::Fim
+    let gramatica = obtem_gramatica(vec!["S -> S' Id(_) Id fim", "S' -> ε"]).unwrap();
+    assert_eq!(gramatica.identificadores().unwrap_err(), vec![
+        "os terminais Id(_) e Id geram o mesmo identificador Tokens::Id",
+        "o terminal fim gera o identificador Tokens::Fim, reservado para o fim da entrada",
+        "os não terminais $inicio e S' geram o mesmo identificador NaoTerminais::SL",
+    ]);
+
+    // só letras e dígitos ASCII ficam no identificador
+    let gramatica = obtem_gramatica(vec!["S -> x² y ação"]).unwrap();
+    assert_eq!(gramatica.identificadores().unwrap().terminais, vec!["XU00B2", "Y", "AU00E7U00E3o"]);
+
+    // o terminal Fim colide com o fim da entrada só no código Rust
+    let gramatica = obtem_gramatica(vec!["S -> Inicio Fim", "Inicio -> Id"]).unwrap();
+    assert_eq!(gramatica.identificadores().unwrap_err(), vec![
+        "o terminal Fim gera o identificador Tokens::Fim, reservado para o fim da entrada",
+    ]);
+}
+
+#[test]
+fn tabela_em_vetores_equivale_as_tabelas() {
+    let gramatica = crate::le_gramatica("gramatica-lia.txt").unwrap();
+    let automato = Automato::constroi(gramatica, Algoritmo::Lalr).unwrap();
+    let identificadores = automato.gramatica.identificadores().unwrap();
+
+    // tipos do liac, com os tokens que carregam um valor
+    let mut tokens: Vec<String> = (0..identificadores.terminais.len())
+        .map(|j| identificadores.padrao_do_terminal(j).replace("(_)", "(0)"))
+        .collect();
+    tokens.push("Tokens::Fim".to_string());
+    let variantes: Vec<String> = tokens.iter().map(|t| t.replace("Tokens::", "").replace("(0)", "(u8)")).collect();
+    let nao_terminais = identificadores.nao_terminais[1..].to_vec();
+    let programa = format!(
+        "#![allow(dead_code)]\n\
+         #[derive(Debug)] pub enum Acoes {{ Empilha(usize), Reduz(usize), Aceita, Erro, VaiPara(usize) }}\n\
+         pub enum Tokens {{ {} }}\n\
+         pub enum NaoTerminais {{ {}, {} }}\n\
+         {}\n\
+         fn main() {{\n\
+             for estado in 0..{} {{\n\
+                 for token in [{}] {{ println!(\"{{:?}}\", acao(estado, &token).unwrap()); }}\n\
+                 for nao_terminal in [{}] {{ println!(\"{{:?}}\", desvio(estado, &nao_terminal).unwrap()); }}\n\
+             }}\n\
+             assert!(acao({}, &Tokens::Fim).is_err());\n\
+         }}\n",
+        variantes.join(", "),
+        identificadores.nao_terminais[0], nao_terminais.join(", "),
+        automato.tabela_rust_em_vetores(&identificadores),
+        automato.estados.len(),
+        tokens.join(", "),
+        nao_terminais.iter().map(|n| format!("NaoTerminais::{}", n)).collect::<Vec<_>>().join(", "),
+        automato.estados.len(),
+    );
+
+    let mut esperado = String::new();
+    for i in 0..automato.estados.len() {
+        for j in 0..tokens.len() {
+            esperado += &format!("{:?}\n", automato.tabela_de_acoes.get(i, j).unwrap());
+        }
+        for j in 1..automato.gramatica.nao_terminais.len() {
+            esperado += &format!("{:?}\n", automato.tabela_de_desvios.get(i, j).unwrap());
+        }
+    }
+
+    assert_eq!(compila_e_executa("vetores", &[("main.rs", &programa)]), esperado);
+}
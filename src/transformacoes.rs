@@ -0,0 +1,295 @@
+use crate::leitor::novo_nome;
+use crate::leitor::Associatividade;
+use crate::{obtem_nao_terminais, obtem_terminais, Gramatica, RegraDeProducao};
+
+// lado direito de uma alternativa e o símbolo do seu %prec
+type Alternativa = (Vec<String>, Option<String>);
+
+// regras agrupadas por não terminal, na ordem em que os não terminais aparecem:
+// (não terminal, alternativas, linha da primeira regra)
+type Grupos = Vec<(String, Vec<Alternativa>, usize)>;
+
+impl Gramatica {
+    // elimina a recursão à esquerda direta e indireta. os não terminais são
+    // visitados na ordem da gramática; em A_i, as alternativas A_i -> A_j γ com
+    // j < i são substituídas pelas alternativas de A_j quando A_j ainda pode
+    // começar por A_i, e a recursão direta A -> A α | β vira
+    //   A -> β A_n
+    //   A_n -> α A_n | ε
+    // como no algoritmo clássico, a recursão escondida atrás de um prefixo
+    // anulável (A -> B A x com B -> ε) não é detectada. cada alternativa nova
+    // herda o %prec da alternativa de que veio
+    pub fn remove_recursao_a_esquerda(&self) -> Gramatica {
+        let mut grupos = self.agrupa();
+        let mut usados = self.simbolos();
+        let originais: Vec<String> = grupos.iter().map(|g| g.0.to_string()).collect();
+
+        for (i, a_i) in originais.iter().enumerate() {
+            for a_j in originais[..i].iter() {
+                if !comeca_por(&grupos, a_j, a_i) {
+                    continue;
+                }
+                let producoes_de_a_j = grupos[posicao(&grupos, a_j)].1.to_vec();
+                let indice = posicao(&grupos, a_i);
+                let mut alternativas: Vec<Alternativa> = Vec::new();
+                for (alternativa, precedencia) in grupos[indice].1.iter() {
+                    if alternativa.first() == Some(a_j) {
+                        for (producao, precedencia_de_a_j) in producoes_de_a_j.iter() {
+                            let mut substituida = producao.to_vec();
+                            substituida.extend(alternativa[1..].iter().cloned());
+                            // em A_i -> A_j, a redução por A_j passa a ser a de A_i
+                            let precedencia = match alternativa.len() {
+                                1 => precedencia.clone().or(precedencia_de_a_j.clone()),
+                                _ => precedencia.clone(),
+                            };
+                            adiciona(&mut alternativas, (substituida, precedencia));
+                        }
+                    } else {
+                        adiciona(&mut alternativas, (alternativa.to_vec(), precedencia.clone()));
+                    }
+                }
+                grupos[indice].1 = alternativas;
+            }
+
+            // recursão direta; A -> A não deriva nada novo e é descartada
+            let indice = posicao(&grupos, a_i);
+            let (recursivas, demais): (Vec<Alternativa>, Vec<Alternativa>) = grupos[indice].1
+                .iter()
+                .filter(|(alternativa, _)| alternativa.len() != 1 || alternativa[0] != *a_i)
+                .cloned()
+                .partition(|(alternativa, _)| alternativa.first() == Some(a_i));
+            if recursivas.is_empty() || demais.is_empty() {
+                continue;
+            }
+            let novo = novo_nome(&mut usados, a_i);
+            grupos[indice].1 = demais
+                .into_iter()
+                .map(|(mut beta, precedencia)| {
+                    beta.push(novo.to_string());
+                    (beta, precedencia)
+                })
+                .collect();
+            let mut auxiliares: Vec<Alternativa> = recursivas
+                .into_iter()
+                .map(|(alternativa, precedencia)| {
+                    let mut alfa = alternativa[1..].to_vec();
+                    alfa.push(novo.to_string());
+                    (alfa, precedencia)
+                })
+                .collect();
+            auxiliares.push((Vec::new(), None));
+            let linha = grupos[indice].2;
+            grupos.insert(indice + 1, (novo, auxiliares, linha));
+        }
+
+        self.de_grupos(grupos)
+    }
+
+    // fatora à esquerda as alternativas de um não terminal que começam pelo
+    // mesmo símbolo: A -> α β1 | α β2 vira
+    //   A -> α A_n
+    //   A_n -> β1 | β2
+    // com α o maior prefixo comum. repete até que nenhum par de alternativas
+    // comece pelo mesmo símbolo, inclusive nos não terminais criados. cada βi
+    // fica com o %prec da sua alternativa, e A -> α A_n com o %prec comum a
+    // todas, se houver
+    pub fn fatora_a_esquerda(&self) -> Gramatica {
+        let mut grupos = self.agrupa();
+        let mut usados = self.simbolos();
+        // não terminal de origem de cada um criado aqui, que dá nome aos
+        // criados a partir dele: I_1 fatorado gera I_2, e não I_1_1
+        let mut origens: Vec<(String, String)> = Vec::new();
+
+        let mut indice = 0;
+        while indice < grupos.len() {
+            // os não terminais criados ficam logo após o fatorado, na ordem
+            let mut criados = 0;
+            loop {
+                let alternativas = &grupos[indice].1;
+                let comum = alternativas.iter().enumerate().find_map(|(i, (alternativa, _))| {
+                    let primeiro = alternativa.first()?;
+                    let fatoradas: Vec<usize> = (i..alternativas.len())
+                        .filter(|j| alternativas[*j].0.first() == Some(primeiro))
+                        .collect();
+                    (fatoradas.len() > 1).then_some(fatoradas)
+                });
+                let Some(fatoradas) = comum else {
+                    break;
+                };
+
+                let mut prefixo = alternativas[fatoradas[0]].0.to_vec();
+                for j in fatoradas[1..].iter() {
+                    let tamanho = prefixo
+                        .iter()
+                        .zip(alternativas[*j].0.iter())
+                        .take_while(|(a, b)| a == b)
+                        .count();
+                    prefixo.truncate(tamanho);
+                }
+                let mut sufixos: Vec<Alternativa> = Vec::new();
+                for j in fatoradas.iter() {
+                    let (alternativa, precedencia) = &alternativas[*j];
+                    adiciona(&mut sufixos, (alternativa[prefixo.len()..].to_vec(), precedencia.clone()));
+                }
+                let precedencia = &alternativas[fatoradas[0]].1;
+                let precedencia = fatoradas
+                    .iter()
+                    .all(|j| alternativas[*j].1 == *precedencia)
+                    .then(|| precedencia.clone())
+                    .flatten();
+
+                let nao_terminal = grupos[indice].0.to_string();
+                let origem = origens
+                    .iter()
+                    .find(|(criado, _)| *criado == nao_terminal)
+                    .map(|(_, origem)| origem.to_string())
+                    .unwrap_or(nao_terminal);
+                let novo = novo_nome(&mut usados, &origem);
+                origens.push((novo.to_string(), origem));
+                prefixo.push(novo.to_string());
+                let mut restantes: Vec<Alternativa> = Vec::new();
+                for (j, alternativa) in grupos[indice].1.iter().enumerate() {
+                    if j == fatoradas[0] {
+                        restantes.push((prefixo.to_vec(), precedencia.clone()));
+                    } else if !fatoradas.contains(&j) {
+                        restantes.push(alternativa.clone());
+                    }
+                }
+                grupos[indice].1 = restantes;
+                let linha = grupos[indice].2;
+                criados += 1;
+                grupos.insert(indice + criados, (novo, sufixos, linha));
+            }
+            indice += 1;
+        }
+
+        self.de_grupos(grupos)
+    }
+
+    // escreve a gramática no formato lido por obtem_regras_de_producao, com as
+    // declarações antes das regras e as alternativas de cada não terminal em
+    // linhas de continuação
+    pub fn escreve(&self) -> String {
+        let mut texto = String::new();
+        if let Some(tokens) = &self.tokens_declarados {
+            texto += &format!("%token {}\n", tokens.join(" "));
+        }
+        // uma linha por nível, do que liga mais fraco ao que liga mais forte
+        let mut niveis: Vec<(usize, Associatividade, Vec<&str>)> = Vec::new();
+        for (simbolo, nivel, associatividade) in self.precedencias.iter() {
+            match niveis.iter_mut().find(|n| n.0 == *nivel) {
+                Some(n) => n.2.push(simbolo),
+                None => niveis.push((*nivel, *associatividade, vec![simbolo])),
+            }
+        }
+        niveis.sort_by_key(|n| n.0);
+        for (_, associatividade, simbolos) in niveis.iter() {
+            let diretiva = match associatividade {
+                Associatividade::Esquerda => "%left",
+                Associatividade::Direita => "%right",
+                Associatividade::NaoAssociativa => "%nonassoc",
+            };
+            texto += &format!("{} {}\n", diretiva, simbolos.join(" "));
+        }
+        let grupos = self.agrupa();
+        let inicial = &self.regras[0].producao[0];
+        if grupos.first().is_some_and(|g| g.0 != *inicial) {
+            texto += &format!("%start {}\n", inicial);
+        }
+        for (nao_terminal, alternativas, _) in grupos.iter() {
+            for (i, (alternativa, precedencia)) in alternativas.iter().enumerate() {
+                let mut lado_direito = if alternativa.is_empty() {
+                    "ε".to_string()
+                } else {
+                    alternativa.join(" ")
+                };
+                if let Some(simbolo) = precedencia {
+                    lado_direito += &format!(" %prec {}", simbolo);
+                }
+                if i == 0 {
+                    texto += &format!("{} -> {}\n", nao_terminal, lado_direito);
+                } else {
+                    texto += &format!("{}| {}\n", " ".repeat(nao_terminal.chars().count() + 1), lado_direito);
+                }
+            }
+        }
+        texto
+    }
+
+    // regras agrupadas por não terminal, sem a regra aumentada
+    fn agrupa(&self) -> Grupos {
+        let mut grupos: Grupos = Vec::new();
+        for regra in self.regras.iter().skip(1) {
+            match grupos.iter_mut().find(|g| g.0 == regra.nao_terminal) {
+                Some(grupo) => grupo.1.push((regra.producao.to_vec(), regra.precedencia.clone())),
+                None => grupos.push((
+                    regra.nao_terminal.to_string(),
+                    vec![(regra.producao.to_vec(), regra.precedencia.clone())],
+                    regra.linha,
+                )),
+            }
+        }
+        grupos
+    }
+
+    fn de_grupos(&self, grupos: Grupos) -> Gramatica {
+        let mut regras: Vec<RegraDeProducao> = vec![self.regras[0].clone()];
+        for (nao_terminal, alternativas, linha) in grupos {
+            for (producao, precedencia) in alternativas {
+                regras.push(RegraDeProducao {
+                    nao_terminal: nao_terminal.to_string(),
+                    producao,
+                    precedencia,
+                    linha,
+                });
+            }
+        }
+        let nao_terminais = obtem_nao_terminais(regras.to_owned());
+        Gramatica {
+            regras: regras.to_owned(),
+            nao_terminais: nao_terminais.to_owned(),
+            terminais: obtem_terminais(regras, nao_terminais),
+            precedencias: self.precedencias.to_vec(),
+            tokens_declarados: self.tokens_declarados.clone(),
+            avisos: Vec::new(),
+        }
+    }
+
+    fn simbolos(&self) -> Vec<String> {
+        let mut simbolos = self.nao_terminais.to_vec();
+        simbolos.extend(self.terminais.iter().cloned());
+        simbolos
+    }
+}
+
+fn posicao(grupos: &Grupos, nao_terminal: &str) -> usize {
+    grupos.iter().position(|g| g.0 == nao_terminal).unwrap()
+}
+
+// se alguma derivação mais à esquerda a partir de origem começa por alvo
+fn comeca_por(grupos: &Grupos, origem: &str, alvo: &str) -> bool {
+    let mut visitados: Vec<String> = vec![origem.to_string()];
+    let mut pendentes: Vec<String> = vec![origem.to_string()];
+    while let Some(atual) = pendentes.pop() {
+        for (alternativa, _) in grupos[posicao(grupos, &atual)].1.iter() {
+            let Some(primeiro) = alternativa.first() else {
+                continue;
+            };
+            if primeiro == alvo {
+                return true;
+            }
+            if !visitados.contains(primeiro) && grupos.iter().any(|g| g.0 == *primeiro) {
+                visitados.push(primeiro.to_string());
+                pendentes.push(primeiro.to_string());
+            }
+        }
+    }
+    false
+}
+
+// acrescenta a alternativa se nenhuma outra tem o mesmo lado direito
+fn adiciona(alternativas: &mut Vec<Alternativa>, alternativa: Alternativa) {
+    if !alternativas.iter().any(|(simbolos, _)| *simbolos == alternativa.0) {
+        alternativas.push(alternativa);
+    }
+}
@@ -0,0 +1,158 @@
+use crate::leitor::ErroDeLeitura;
+use crate::{Gramatica, FIM_DA_ENTRADA, INICIO_AUMENTADO};
+
+impl Gramatica {
+    // avisos sobre partes da gramática que nunca participam de uma derivação:
+    // não terminais improdutivos (que não derivam nenhuma sequência de
+    // terminais), inalcançáveis a partir do símbolo inicial e regras que usam
+    // algum não terminal improdutivo. a regra aumentada não é verificada
+    pub fn verifica(&self) -> Vec<ErroDeLeitura> {
+        let mut avisos: Vec<ErroDeLeitura> = Vec::new();
+
+        // produtivos: repete até que nenhum não terminal novo seja encontrado
+        let mut produtivos = vec![false; self.nao_terminais.len()];
+        let mut mudou = true;
+        while mudou {
+            mudou = false;
+            for regra in &self.regras {
+                let a = self.indice_nao_terminal(&regra.nao_terminal).unwrap();
+                if !produtivos[a] && self.regra_utilizavel(&regra.producao, &produtivos) {
+                    produtivos[a] = true;
+                    mudou = true;
+                }
+            }
+        }
+
+        // alcançáveis a partir do símbolo inicial, apenas por regras utilizáveis
+        let mut alcancaveis = vec![false; self.nao_terminais.len()];
+        if !alcancaveis.is_empty() {
+            alcancaveis[0] = true;
+        }
+        mudou = true;
+        while mudou {
+            mudou = false;
+            for regra in &self.regras {
+                let a = self.indice_nao_terminal(&regra.nao_terminal).unwrap();
+                if !alcancaveis[a] || !self.regra_utilizavel(&regra.producao, &produtivos) {
+                    continue;
+                }
+                for simbolo in &regra.producao {
+                    if let Some(b) = self.indice_nao_terminal(simbolo) {
+                        if !alcancaveis[b] {
+                            alcancaveis[b] = true;
+                            mudou = true;
+                        }
+                    }
+                }
+            }
+        }
+
+        for (a, nao_terminal) in self.nao_terminais.iter().enumerate().skip(1) {
+            let regras: Vec<usize> = (1..self.regras.len())
+                .filter(|r| self.regras[*r].nao_terminal == *nao_terminal)
+                .collect();
+            let mensagem = if !produtivos[a] {
+                format!("não terminal {} não deriva nenhuma sequência de terminais", nao_terminal)
+            } else if !alcancaveis[a] {
+                format!("não terminal {} não é alcançável a partir do símbolo inicial", nao_terminal)
+            } else {
+                continue;
+            };
+            avisos.push(ErroDeLeitura {
+                linha: self.regras[regras[0]].linha,
+                coluna: 1,
+                mensagem: format!("{}; regra(s) nunca usada(s): {}", mensagem, self.descreve_regras(&regras)),
+            });
+        }
+
+        // regras de não terminais úteis que dependem de um improdutivo
+        for (r, regra) in self.regras.iter().enumerate().skip(1) {
+            let a = self.indice_nao_terminal(&regra.nao_terminal).unwrap();
+            if !produtivos[a] || !alcancaveis[a] || self.regra_utilizavel(&regra.producao, &produtivos) {
+                continue;
+            }
+            let improdutivo = regra.producao
+                .iter()
+                .find(|s| self.indice_nao_terminal(s).is_some_and(|b| !produtivos[b]))
+                .unwrap();
+            avisos.push(ErroDeLeitura {
+                linha: regra.linha,
+                coluna: 1,
+                mensagem: format!(
+                    "a regra {} nunca é usada, {} não deriva nenhuma sequência de terminais",
+                    self.descreve_regras(&[r]), improdutivo,
+                ),
+            });
+        }
+
+        avisos
+    }
+
+    // problemas que impedem a construção do autômato: a regra aumentada fora
+    // do lugar, símbolos que não estão entre os terminais e não terminais, não
+    // terminais sem regras e o $ entre os terminais. a leitura nunca produz
+    // esses problemas, mas uma gramática montada por outro caminho pode
+    pub(crate) fn valida(&self) -> Vec<String> {
+        let mut erros: Vec<String> = Vec::new();
+        let inicio_valido = match self.regras.first() {
+            Some(regra) => {
+                regra.nao_terminal == INICIO_AUMENTADO
+                    && regra.producao.len() == 1
+                    && self.indice_nao_terminal(&regra.producao[0]).is_some_and(|i| i > 0)
+            }
+            None => false,
+        };
+        if !inicio_valido || self.nao_terminais.first().map(String::as_str) != Some(INICIO_AUMENTADO) {
+            erros.push(format!(
+                "esperada a regra aumentada {} -> S como R0, com {} como primeiro não terminal",
+                INICIO_AUMENTADO, INICIO_AUMENTADO,
+            ));
+        }
+
+        for (i, regra) in self.regras.iter().enumerate().skip(1) {
+            if regra.nao_terminal == INICIO_AUMENTADO {
+                erros.push(format!("a regra R{} repete o não terminal {} da regra aumentada", i, INICIO_AUMENTADO));
+            }
+        }
+        for (i, regra) in self.regras.iter().enumerate() {
+            if self.indice_nao_terminal(&regra.nao_terminal).is_none() {
+                erros.push(format!("o lado esquerdo {} da regra R{} não é um não terminal", regra.nao_terminal, i));
+            }
+            for simbolo in regra.producao.iter() {
+                if !self.terminais.contains(simbolo) && self.indice_nao_terminal(simbolo).is_none() {
+                    erros.push(format!("o símbolo {} da regra R{} não é terminal nem não terminal", simbolo, i));
+                }
+            }
+        }
+        for nao_terminal in self.nao_terminais.iter() {
+            if !self.regras.iter().any(|r| r.nao_terminal == *nao_terminal) {
+                erros.push(format!("o não terminal {} não tem regras", nao_terminal));
+            }
+        }
+        for terminal in self.terminais.iter() {
+            if terminal == FIM_DA_ENTRADA {
+                erros.push(format!("o terminal {} é reservado para o fim da entrada", FIM_DA_ENTRADA));
+            } else if self.indice_nao_terminal(terminal).is_some() {
+                erros.push(format!("o símbolo {} é terminal e não terminal", terminal));
+            }
+        }
+        erros
+    }
+
+    // uma regra só pode ser usada se todos os não terminais do lado direito
+    // forem produtivos
+    fn regra_utilizavel(&self, producao: &[String], produtivos: &[bool]) -> bool {
+        producao
+            .iter()
+            .all(|s| self.indice_nao_terminal(s).is_none_or(|b| produtivos[b]))
+    }
+
+    // lista de regras no formato R5 (linha 7), R6 (linha 8)
+    fn descreve_regras(&self, regras: &[usize]) -> String {
+        regras
+            .iter()
+            .map(|r| format!("R{} (linha {})", r, self.regras[*r].linha))
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+}
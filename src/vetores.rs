@@ -0,0 +1,92 @@
+use crate::{Acao, Automato, Identificadores};
+
+// decodificação das células, igual para toda gramática
+const CONSULTAS: &str = "\
+pub fn acao(estado: usize, token: &Tokens) -> Result<Acoes, ()> {
+    let linha = ACOES.get(estado).ok_or(())?;
+    Ok(match coluna_do_token(token).map(|coluna| linha[coluna]) {
+        None | Some(0) => Acoes::Erro,
+        Some(ACEITA) => Acoes::Aceita,
+        Some(celula) if celula > 0 => Acoes::Empilha(celula as usize),
+        Some(celula) => Acoes::Reduz(-celula as usize),
+    })
+}
+
+pub fn desvio(estado: usize, nao_terminal: &NaoTerminais) -> Result<Acoes, ()> {
+    let linha = DESVIOS.get(estado).ok_or(())?;
+    Ok(match coluna_do_nao_terminal(nao_terminal).map(|coluna| linha[coluna]) {
+        None | Some(0) => Acoes::Erro,
+        Some(destino) => Acoes::VaiPara(destino),
+    })
+}
+";
+
+impl Automato {
+    // alternativa ao trecho de tabela_rust para o liac: as tabelas ACTION e
+    // GOTO em vetores estáticos, uma linha por estado, e as funções acao e
+    // desvio, que devolvem as mesmas Acoes. o estado 0 nunca é destino de uma
+    // transição, então 0 marca as células vazias
+    pub fn tabela_rust_em_vetores(&self, identificadores: &Identificadores) -> String {
+        let terminais = self.gramatica.terminais.len();
+        let nao_terminais = self.gramatica.nao_terminais.len() - 1;
+        let mut string: String = String::new();
+
+        string += format!("pub static PRODUCOES: [(NaoTerminais, usize); {}] = [\n", self.gramatica.regras.len()).as_ref();
+        for (r, regra) in self.gramatica.regras.iter().enumerate() {
+            let nao_terminal = self.gramatica.indice_nao_terminal(&regra.nao_terminal).unwrap();
+            string += format!(
+                "    (NaoTerminais::{}, {}), // R{}\n",
+                identificadores.nao_terminais[nao_terminal], regra.producao.len(), r,
+            ).as_ref();
+        }
+        string += "];\n\n";
+
+        string += "// células de ACOES: 0 é erro, n > 0 empilha e vai para o estado n, -r reduz\n\
+                   // pela regra r e ACEITA aceita. células de DESVIOS: o estado destino, ou 0\n\
+                   const ACEITA: i32 = i32::MIN;\n\n";
+
+        string += format!("static ACOES: [[i32; {}]; {}] = [\n", terminais + 1, self.estados.len()).as_ref();
+        for i in 0..self.estados.len() {
+            let celulas: Vec<String> = (0..=terminais)
+                .map(|j| match self.tabela_de_acoes.get(i, j).unwrap() {
+                    Acao::Empilha(estado) => estado.to_string(),
+                    Acao::Reduz(regra) => format!("-{}", regra),
+                    Acao::Aceita => "ACEITA".to_string(),
+                    _ => "0".to_string(),
+                })
+                .collect();
+            string += format!("    [{}], // I{}\n", celulas.join(", "), i).as_ref();
+        }
+        string += "];\n\n";
+
+        string += format!("static DESVIOS: [[usize; {}]; {}] = [\n", nao_terminais, self.estados.len()).as_ref();
+        for i in 0..self.estados.len() {
+            let celulas: Vec<String> = (1..=nao_terminais)
+                .map(|j| match self.tabela_de_desvios.get(i, j).unwrap() {
+                    Acao::VaiPara(estado) => estado.to_string(),
+                    _ => "0".to_string(),
+                })
+                .collect();
+            string += format!("    [{}], // I{}\n", celulas.join(", "), i).as_ref();
+        }
+        string += "];\n\n";
+
+        // variantes que não são da gramática não têm coluna
+        string += "#[allow(unreachable_patterns)]\nfn coluna_do_token(token: &Tokens) -> Option<usize> {\n    \
+                   match token {\n";
+        for j in 0..terminais {
+            string += format!("        {} => Some({}),\n", identificadores.padrao_do_terminal(j), j).as_ref();
+        }
+        string += format!("        Tokens::Fim => Some({}),\n        _ => None,\n    }}\n}}\n\n", terminais).as_ref();
+
+        string += "#[allow(unreachable_patterns)]\nfn coluna_do_nao_terminal(nao_terminal: &NaoTerminais) -> Option<usize> {\n    \
+                   match nao_terminal {\n";
+        for j in 1..=nao_terminais {
+            string += format!("        NaoTerminais::{} => Some({}),\n", identificadores.nao_terminais[j], j - 1).as_ref();
+        }
+        string += "        _ => None,\n    }\n}\n\n";
+
+        string += CONSULTAS;
+        string
+    }
+}
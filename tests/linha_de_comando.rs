@@ -0,0 +1,49 @@
+use std::process::{Command, Output};
+
+// executa o programa sobre a gramática dada, escrita num arquivo temporário
+fn executa(nome: &str, gramatica: &str, argumentos: &[&str]) -> Output {
+    let arquivo = std::env::temp_dir().join(format!("slr1aux-{}-{}.txt", nome, std::process::id()));
+    std::fs::write(&arquivo, gramatica).unwrap();
+    let saida = Command::new(env!("CARGO_BIN_EXE_slr1aux"))
+        .args(argumentos)
+        .arg(&arquivo)
+        .output()
+        .unwrap();
+    std::fs::remove_file(&arquivo).unwrap();
+    saida
+}
+
+#[test]
+fn conflitos_sao_relatados_e_encerram_com_codigo_1() {
+    let saida = executa("conflito", "E -> E Mais E | Id\n", &[]);
+    assert_eq!(saida.status.code(), Some(1));
+    let erros = String::from_utf8_lossy(&saida.stderr);
+    assert!(erros.starts_with(
+        "conflito empilha/reduz no estado I3 sob o símbolo Mais:\n  \
+         I2:\n    E -> E .Mais E\n  \
+         R1:\n    E -> E Mais E.\n  \
+         ação mantida na tabela: I2\n",
+    ));
+    assert!(erros.ends_with("1 conflito(s) não resolvido(s)\n"));
+    // o relatório é escrito mesmo com conflitos
+    assert!(String::from_utf8_lossy(&saida.stdout).contains("| I3 |"));
+
+    let saida = executa("sem-conflito", "E -> E Mais T | T\nT -> Id\n", &[]);
+    assert_eq!(saida.status.code(), Some(0));
+    assert!(saida.stderr.is_empty());
+}
+
+#[test]
+fn colisao_de_identificadores_e_aviso_no_relatorio_e_erro_no_modulo() {
+    let gramatica = "S -> Id(_) Id\n";
+    let saida = executa("colisao", gramatica, &[]);
+    assert_eq!(saida.status.code(), Some(0));
+    let erros = String::from_utf8_lossy(&saida.stderr);
+    assert!(erros.contains(": aviso: os terminais Id(_) e Id geram o mesmo identificador Tokens::Id\n"));
+    assert!(!erros.contains("erro"));
+
+    let saida = executa("colisao-modulo", gramatica, &["--rust-module"]);
+    assert_eq!(saida.status.code(), Some(3));
+    assert!(String::from_utf8_lossy(&saida.stderr).contains(": erro: os terminais Id(_) e Id"));
+    assert!(saida.stdout.is_empty());
+}